@@ -0,0 +1,64 @@
+//Measures what a rollback costs, load the oldest saved Round then advance and save it forward 8 frames
+//Run with `cargo bench --no-default-features --bench rollback`
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use storm_fighting::*;
+
+const ROLLBACK_FRAMES: usize = 8;
+const WARMUP_FRAMES: usize = 600;
+const ITERATIONS: usize = 10_000;
+
+//Mashes buttons the same way every run, so the Round has projectiles and effects in it
+struct InputGenerator {
+    seed: u32
+}
+
+impl InputGenerator {
+    fn next(&mut self) -> [NetInput; 2] {
+        let mut inputs = [NetInput { input: 0 }; 2];
+        for input in inputs.iter_mut() {
+            self.seed = self.seed.wrapping_mul(1103515245).wrapping_add(12345);
+            input.input = ((self.seed >> 16) & 0x3ff) as u16;
+        }
+        return inputs;
+    }
+}
+
+fn main() {
+    let character_sheet: CharacterSheet = serde_json::from_str(include_str!("../resources/ryu_character_sheet.json")).unwrap();
+    let gameplay_config = GameplayConfig::new_from_sheet(character_sheet).unwrap();
+    let mut round = Round::new_with_animation_lib(&gameplay_config.animation_configs);
+    let mut input_generator = InputGenerator { seed: 12345 };
+
+    for _ in 0..WARMUP_FRAMES {
+        round.advance(input_generator.next(), &gameplay_config);
+    }
+
+    //The same ring of saved states GGRS keeps, one slot per frame we might roll back to
+    let mut saved_states = [round; ROLLBACK_FRAMES + 1];
+    let mut inputs = [[NetInput { input: 0 }; 2]; ROLLBACK_FRAMES];
+    for frame_inputs in inputs.iter_mut() {
+        *frame_inputs = input_generator.next();
+    }
+
+    let mut total = Duration::ZERO;
+    let mut worst = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+
+        round = black_box(saved_states[0]);
+        for (frame, frame_inputs) in inputs.iter().enumerate() {
+            round.advance(*frame_inputs, &gameplay_config);
+            saved_states[frame + 1] = black_box(round);
+        }
+
+        let elapsed = start.elapsed();
+        total += elapsed;
+        worst = worst.max(elapsed);
+    }
+
+    println!("Round snapshot size: {} bytes", std::mem::size_of::<Round>());
+    println!("{} frame rollback (load + {} x advance + save) over {} iterations", ROLLBACK_FRAMES, ROLLBACK_FRAMES, ITERATIONS);
+    println!("    mean: {:?}", total / ITERATIONS as u32);
+    println!("    worst: {:?}", worst);
+}
@@ -31,12 +31,37 @@ pub mod matchbox {
         NewPeer(PeerId),
         Signal { sender: PeerId, data: S },
     }
+
+    /// Events about the room a peer is in, they aren't relayed from other peers
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+    pub enum RoomEvent {
+        /// The private room was made, hand this code to whoever should join it
+        RoomCreated(String),
+        /// The private room could not be joined, the connection is closed after this
+        RoomError(String),
+        /// The name and pallete another peer in the room gave with `?name=` and `?pallete=`, sent
+        /// before it is announced with `PeerEvent::NewPeer` so both sides know who they are connecting to
+        PeerProfile {
+            peer: PeerId,
+            name: String,
+            #[serde(default)]
+            pallete: usize,
+        },
+    }
 }
 use matchbox::*;
 
 type PeerRequest = matchbox::PeerRequest<serde_json::Value>;
 type PeerEvent = matchbox::PeerEvent<serde_json::Value>;
 
+/// Private rooms hold exactly two peers
+const PRIVATE_ROOM_SIZE: usize = 2;
+/// Room codes get read out loud, so they are short and leave out characters that look alike
+const ROOM_CODE_LENGTH: usize = 5;
+const ROOM_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+/// Longer names are cut short, they only need to fit in a game's HUD
+const MAX_PEER_NAME_LENGTH: usize = 16;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct RoomId(String);
 
@@ -44,16 +69,38 @@ pub(crate) struct RoomId(String);
 pub(crate) struct RequestedRoom {
     id: RoomId,
     next: Option<usize>,
+    private: Option<PrivateRoomRequest>,
+}
+
+/// `?private=create` makes a new private room, the room id in the path is ignored and the
+/// server picks a code instead. `/<code>?private=join` joins the private room with that code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PrivateRoomRequest {
+    Create,
+    Join,
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
 pub(crate) struct QueryParam {
     next: Option<usize>,
+    private: Option<PrivateRoomRequest>,
+    name: Option<String>,
+    pallete: Option<usize>,
+}
+
+#[derive(Default)]
+pub(crate) struct PrivateRoom {
+    peers: HashSet<PeerId>,
+    /// Once a second peer has joined, the room turns everyone else away until it is empty
+    filled: bool,
 }
 
 pub(crate) struct Peer {
     pub uuid: PeerId,
     pub room: RequestedRoom,
+    pub name: Option<String>,
+    pub pallete: usize,
     pub sender: tokio::sync::mpsc::UnboundedSender<std::result::Result<Message, warp::Error>>,
 }
 
@@ -61,6 +108,17 @@ pub(crate) struct Peer {
 pub(crate) struct State {
     clients: HashMap<PeerId, Peer>,
     rooms: HashMap<RequestedRoom, HashSet<PeerId>>,
+    private_rooms: HashMap<RoomId, PrivateRoom>,
+}
+
+fn room_code_from_random(random: u128) -> String {
+    let mut random = random;
+    let mut code = String::with_capacity(ROOM_CODE_LENGTH);
+    for _ in 0..ROOM_CODE_LENGTH {
+        code.push(ROOM_CODE_ALPHABET[(random % ROOM_CODE_ALPHABET.len() as u128) as usize] as char);
+        random /= ROOM_CODE_ALPHABET.len() as u128;
+    }
+    code
 }
 
 impl State {
@@ -88,12 +146,56 @@ impl State {
         }
     }
 
+    /// Makes a private room with a fresh code and puts the peer in it, returns the code
+    fn create_private_room(&mut self, mut peer: Peer) -> RoomId {
+        let mut code = RoomId(room_code_from_random(uuid::Uuid::new_v4().as_u128()));
+        while self.private_rooms.contains_key(&code) {
+            code = RoomId(room_code_from_random(uuid::Uuid::new_v4().as_u128()));
+        }
+
+        let mut room = PrivateRoom::default();
+        room.peers.insert(peer.uuid.clone());
+        self.private_rooms.insert(code.clone(), room);
+
+        peer.room.id = code.clone();
+        self.clients.insert(peer.uuid.clone(), peer);
+        code
+    }
+
+    /// Returns peers already in the private room, or why the peer can't join it
+    fn join_private_room(&mut self, peer: Peer) -> Result<Vec<PeerId>, String> {
+        let room = match self.private_rooms.get_mut(&peer.room.id) {
+            Some(room) => room,
+            None => return Err("no such room".to_string()),
+        };
+        if room.filled {
+            return Err("room full".to_string());
+        }
+
+        let ret = room.peers.iter().cloned().collect();
+        room.peers.insert(peer.uuid.clone());
+        room.filled = room.peers.len() == PRIVATE_ROOM_SIZE;
+        self.clients.insert(peer.uuid.clone(), peer);
+        Ok(ret)
+    }
+
     fn remove_peer(&mut self, peer_id: &PeerId) {
         let peer = self
             .clients
             .remove(peer_id)
             .expect("Couldn't find uuid to remove");
 
+        if peer.room.private.is_some() {
+            if let Some(room) = self.private_rooms.get_mut(&peer.room.id) {
+                room.peers.remove(peer_id);
+                // A room nobody is in is gone, it reports "no such room" from now on
+                if room.peers.is_empty() {
+                    self.private_rooms.remove(&peer.room.id);
+                }
+            }
+            return;
+        }
+
         let room_peers = self.rooms.get_mut(&peer.room);
 
         if let Some(room_peers) = room_peers {
@@ -120,13 +222,18 @@ fn parse_room_id(id: String) -> RoomId {
     RoomId(id)
 }
 
+/// Room codes are typed in by hand, so case and stray spaces don't matter
+fn parse_room_code(id: RoomId) -> RoomId {
+    RoomId(id.0.trim().to_uppercase())
+}
+
 pub(crate) fn ws_filter(
     state: Arc<Mutex<State>>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     warp::ws()
         .and(warp::any())
         .and(warp::path::param().map(parse_room_id))
-        .and(warp::query::<QueryParam>().map(parse_room_next))
+        .and(warp::query::<QueryParam>())
         .and(with_state(state))
         .and_then(ws_handler)
 }
@@ -135,6 +242,10 @@ fn parse_room_next(p: QueryParam) -> Option<usize> {
     p.next
 }
 
+fn parse_peer_name(name: &str) -> String {
+    name.trim().chars().take(MAX_PEER_NAME_LENGTH).collect()
+}
+
 fn with_state(
     state: Arc<Mutex<State>>,
 ) -> impl Filter<Extract = (Arc<Mutex<State>>,), Error = Infallible> + Clone {
@@ -144,11 +255,25 @@ fn with_state(
 pub(crate) async fn ws_handler(
     ws: warp::ws::Ws,
     room_id: RoomId,
-    next: Option<usize>,
+    query: QueryParam,
     state: Arc<Mutex<State>>,
 ) -> std::result::Result<impl Reply, Rejection> {
+    let private = query.private;
+    let name = query.name.as_deref().map(parse_peer_name);
+    let pallete = query.pallete.unwrap_or(0);
+    let next = parse_room_next(query);
+    let id = match private {
+        Some(_) => parse_room_code(room_id),
+        None => room_id,
+    };
     Ok(ws.on_upgrade(move |websocket| {
-        handle_ws(websocket, state, RequestedRoom { id: room_id, next })
+        handle_ws(
+            websocket,
+            state,
+            RequestedRoom { id, next, private },
+            name,
+            pallete,
+        )
     }))
 }
 
@@ -190,7 +315,26 @@ fn spawn_sender_task(
     client_sender
 }
 
-async fn handle_ws(websocket: WebSocket, state: Arc<Mutex<State>>, requested_room: RequestedRoom) {
+fn room_event_message(event: &RoomEvent) -> Message {
+    Message::text(serde_json::to_string(event).expect("error serializing message"))
+}
+
+fn send_room_event(
+    sender: &mpsc::UnboundedSender<std::result::Result<Message, warp::Error>>,
+    event: &RoomEvent,
+) {
+    if let Err(e) = sender.send(Ok(room_event_message(event))) {
+        error!("Error sending message {:?}", e);
+    }
+}
+
+async fn handle_ws(
+    websocket: WebSocket,
+    state: Arc<Mutex<State>>,
+    requested_room: RequestedRoom,
+    name: Option<String>,
+    pallete: usize,
+) {
     let (ws_sender, mut ws_receiver) = websocket.split();
     let sender = spawn_sender_task(ws_sender);
     let mut peer_uuid = None;
@@ -222,20 +366,64 @@ async fn handle_ws(websocket: WebSocket, state: Arc<Mutex<State>>, requested_roo
                     error!("client set uuid more than once");
                     continue;
                 }
-                peer_uuid = Some(id.clone());
 
                 let mut state = state.lock().await;
-                let peers = state.add_peer(Peer {
+                let peer = Peer {
                     uuid: id.clone(),
                     sender: sender.clone(),
                     room: requested_room.clone(),
-                });
+                    name: name.clone(),
+                    pallete,
+                };
+                let peers = match requested_room.private {
+                    None => state.add_peer(peer),
+                    Some(PrivateRoomRequest::Create) => {
+                        let code = state.create_private_room(peer);
+                        info!("{:?} created private room {:?}", id, code);
+                        send_room_event(&sender, &RoomEvent::RoomCreated(code.0));
+                        vec![]
+                    }
+                    Some(PrivateRoomRequest::Join) => match state.join_private_room(peer) {
+                        Ok(peers) => peers,
+                        Err(reason) => {
+                            warn!("{:?} can't join {:?}: {}", id, requested_room.id, reason);
+                            send_room_event(&sender, &RoomEvent::RoomError(reason));
+                            let _ = sender.send(Ok(Message::close()));
+                            break;
+                        }
+                    },
+                };
+                peer_uuid = Some(id.clone());
 
                 let event = Message::text(
                     serde_json::to_string(&PeerEvent::NewPeer(id.clone()))
                         .expect("error serializing message"),
                 );
 
+                // Introduce the peers that gave a name to each other, before they start connecting
+                for peer_id in peers.iter() {
+                    if let Some(name) = &name {
+                        let event = RoomEvent::PeerProfile {
+                            peer: id.clone(),
+                            name: name.clone(),
+                            pallete,
+                        };
+                        state.try_send(peer_id, room_event_message(&event));
+                    }
+                    let peer_profile = state
+                        .clients
+                        .get(peer_id)
+                        .and_then(|p| p.name.clone().map(|name| (name, p.pallete)));
+                    if let Some((peer_name, peer_pallete)) = peer_profile {
+                        let event = RoomEvent::PeerProfile {
+                            peer: peer_id.clone(),
+                            name: peer_name,
+                            pallete: peer_pallete,
+                        };
+                        send_room_event(&sender, &event);
+                    }
+                }
+
                 for peer_id in peers {
                     // Tell everyone about this new peer
                     info!("{:?} -> {:?}", peer_id, event.to_str().unwrap());
@@ -283,7 +471,9 @@ mod tests {
     use tokio::{select, time};
     use warp::{test::WsClient, ws::Message, Filter, Rejection, Reply};
 
-    use crate::signaling::{parse_room_id, parse_room_next, PeerEvent, QueryParam, RoomId};
+    use crate::signaling::{
+        parse_peer_name, parse_room_id, parse_room_next, PeerEvent, QueryParam, RoomEvent, RoomId,
+    };
 
     fn api() -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         super::ws_filter(Default::default())
@@ -641,6 +831,152 @@ mod tests {
         }
     }
 
+    async fn recv_room_event(client: &mut WsClient) -> RoomEvent {
+        let message = client.recv().await;
+        serde_json::from_str(message.unwrap().to_str().unwrap()).unwrap()
+    }
+
+    async fn create_private_room(
+        api: impl Filter<Extract = impl Reply, Error = Rejection> + Clone + Send + Sync + 'static,
+    ) -> (WsClient, String) {
+        let mut creator = warp::test::ws()
+            .path("/anything?private=create")
+            .handshake(api)
+            .await
+            .expect("handshake");
+
+        creator
+            .send(Message::text(r#"{"Uuid": "uuid-a"}"#.to_string()))
+            .await;
+
+        let code = match recv_room_event(&mut creator).await {
+            RoomEvent::RoomCreated(code) => code,
+            event => panic!("unexpected event {:?}", event),
+        };
+        (creator, code)
+    }
+
+    #[tokio::test]
+    async fn private_room_pairs() {
+        let _ = pretty_env_logger::try_init();
+        let api = api();
+
+        let (mut client_a, code) = create_private_room(api.clone()).await;
+        assert_eq!(code.len(), 5);
+
+        let mut client_b = warp::test::ws()
+            .path(&format!("/{}?private=join", code.to_lowercase()))
+            .handshake(api.clone())
+            .await
+            .expect("handshake");
+
+        client_b
+            .send(Message::text(r#"{"Uuid": "uuid-b"}"#.to_string()))
+            .await;
+
+        let new_peer_b = recv_peer_event(&mut client_a).await;
+        assert_eq!(new_peer_b, PeerEvent::NewPeer("uuid-b".to_string()));
+    }
+
+    #[tokio::test]
+    async fn peer_profiles() {
+        let _ = pretty_env_logger::try_init();
+        let api = api();
+
+        let mut client_a = warp::test::ws()
+            .path("/room_name?next=2&name=Ryu&pallete=2")
+            .handshake(api.clone())
+            .await
+            .expect("handshake");
+
+        client_a
+            .send(Message::text(r#"{"Uuid": "uuid-a"}"#.to_string()))
+            .await;
+
+        let mut client_b = warp::test::ws()
+            .path("/room_name?next=2&name=Ken%20M")
+            .handshake(api.clone())
+            .await
+            .expect("handshake");
+
+        client_b
+            .send(Message::text(r#"{"Uuid": "uuid-b"}"#.to_string()))
+            .await;
+
+        // Both sides hear the other's name, and a hears it before being told to connect to b
+        let profile_b = recv_room_event(&mut client_a).await;
+        let new_peer_b = recv_peer_event(&mut client_a).await;
+        let profile_a = recv_room_event(&mut client_b).await;
+
+        assert_eq!(
+            profile_b,
+            RoomEvent::PeerProfile {
+                peer: "uuid-b".to_string(),
+                name: "Ken M".to_string(),
+                pallete: 0
+            }
+        );
+        assert_eq!(new_peer_b, PeerEvent::NewPeer("uuid-b".to_string()));
+        assert_eq!(
+            profile_a,
+            RoomEvent::PeerProfile {
+                peer: "uuid-a".to_string(),
+                name: "Ryu".to_string(),
+                pallete: 2
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn private_room_full() {
+        let _ = pretty_env_logger::try_init();
+        let api = api();
+
+        let (_client_a, code) = create_private_room(api.clone()).await;
+
+        let mut client_b = warp::test::ws()
+            .path(&format!("/{}?private=join", code))
+            .handshake(api.clone())
+            .await
+            .expect("handshake");
+
+        client_b
+            .send(Message::text(r#"{"Uuid": "uuid-b"}"#.to_string()))
+            .await;
+
+        let mut client_c = warp::test::ws()
+            .path(&format!("/{}?private=join", code))
+            .handshake(api.clone())
+            .await
+            .expect("handshake");
+
+        client_c
+            .send(Message::text(r#"{"Uuid": "uuid-c"}"#.to_string()))
+            .await;
+
+        let room_error = recv_room_event(&mut client_c).await;
+        assert_eq!(room_error, RoomEvent::RoomError("room full".to_string()));
+    }
+
+    #[tokio::test]
+    async fn private_room_missing() {
+        let _ = pretty_env_logger::try_init();
+        let api = api();
+
+        let mut client_a = warp::test::ws()
+            .path("/NOPE2?private=join")
+            .handshake(api)
+            .await
+            .expect("handshake");
+
+        client_a
+            .send(Message::text(r#"{"Uuid": "uuid-a"}"#.to_string()))
+            .await;
+
+        let room_error = recv_room_event(&mut client_a).await;
+        assert_eq!(room_error, RoomEvent::RoomError("no such room".to_string()));
+    }
+
     #[test]
     fn requested_room() {
         assert_eq!(
@@ -650,7 +986,31 @@ mod tests {
     }
     #[test]
     fn requested_scope() {
-        assert_eq!(parse_room_next(QueryParam { next: Some(3) }), Some(3));
-        assert_eq!(parse_room_next(QueryParam { next: None }), None);
+        assert_eq!(
+            parse_room_next(QueryParam {
+                next: Some(3),
+                private: None,
+                name: None,
+                pallete: None
+            }),
+            Some(3)
+        );
+        assert_eq!(
+            parse_room_next(QueryParam {
+                next: None,
+                private: None,
+                name: None,
+                pallete: None
+            }),
+            None
+        );
+    }
+    #[test]
+    fn peer_name() {
+        assert_eq!(parse_peer_name(" Ryu "), "Ryu".to_string());
+        assert_eq!(
+            parse_peer_name("a name that is far too long"),
+            "a name that is f".to_string()
+        );
     }
 }
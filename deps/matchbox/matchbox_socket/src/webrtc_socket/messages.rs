@@ -9,6 +9,22 @@ pub enum PeerEvent {
     Signal { sender: PeerId, data: PeerSignal },
 }
 
+/// Events about the room a peer is in, go from signalling server to peer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RoomEvent {
+    /// The private room was made, hand this code to whoever should join it
+    RoomCreated(String),
+    /// The private room couldn't be joined, the signalling server closes the connection after this
+    RoomError(String),
+    /// The name and pallete another peer in the room connected with, arrives before that peer does
+    PeerProfile {
+        peer: PeerId,
+        name: String,
+        #[serde(default)]
+        pallete: usize,
+    },
+}
+
 // TODO: move back into lib
 /// Requests go from peer to signalling server
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
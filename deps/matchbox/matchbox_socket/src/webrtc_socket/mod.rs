@@ -31,6 +31,7 @@ use native::*;
 #[cfg(target_arch = "wasm32")]
 use wasm::*;
 
+pub use messages::RoomEvent;
 use messages::*;
 use uuid::Uuid;
 
@@ -71,6 +72,7 @@ impl Default for WebRtcSocketConfig {
 pub struct WebRtcSocket {
     messages_from_peers: futures_channel::mpsc::UnboundedReceiver<(PeerId, Packet)>,
     new_connected_peers: futures_channel::mpsc::UnboundedReceiver<PeerId>,
+    room_events: futures_channel::mpsc::UnboundedReceiver<RoomEvent>,
     peer_messages_out: futures_channel::mpsc::UnboundedSender<(PeerId, Packet)>,
     peers: Vec<PeerId>,
     id: PeerId,
@@ -95,6 +97,7 @@ impl WebRtcSocket {
     pub fn new_with_config(config: WebRtcSocketConfig) -> (Self, MessageLoopFuture) {
         let (messages_from_peers_tx, messages_from_peers) = futures_channel::mpsc::unbounded();
         let (new_connected_peers_tx, new_connected_peers) = futures_channel::mpsc::unbounded();
+        let (room_events_tx, room_events) = futures_channel::mpsc::unbounded();
         let (peer_messages_out_tx, peer_messages_out_rx) =
             futures_channel::mpsc::unbounded::<(PeerId, Packet)>();
 
@@ -107,6 +110,7 @@ impl WebRtcSocket {
                 messages_from_peers,
                 peer_messages_out: peer_messages_out_tx,
                 new_connected_peers,
+                room_events,
                 peers: vec![],
             },
             Box::pin(run_socket(
@@ -115,6 +119,7 @@ impl WebRtcSocket {
                 peer_messages_out_rx,
                 new_connected_peers_tx,
                 messages_from_peers_tx,
+                room_events_tx,
             )),
         )
     }
@@ -142,6 +147,15 @@ impl WebRtcSocket {
         ids
     }
 
+    /// Room events received since the last call, see `RoomEvent`
+    pub fn room_events(&mut self) -> Vec<RoomEvent> {
+        let mut events = Vec::new();
+        while let Ok(Some(event)) = self.room_events.try_next() {
+            events.push(event);
+        }
+        events
+    }
+
     pub fn connected_peers(&self) -> Vec<PeerId> {
         self.peers.clone() // TODO: could probably be an iterator or reference instead?
     }
@@ -174,14 +188,19 @@ async fn run_socket(
     peer_messages_out_rx: futures_channel::mpsc::UnboundedReceiver<(PeerId, Packet)>,
     new_connected_peers_tx: futures_channel::mpsc::UnboundedSender<PeerId>,
     messages_from_peers_tx: futures_channel::mpsc::UnboundedSender<(PeerId, Packet)>,
+    room_events_tx: futures_channel::mpsc::UnboundedSender<RoomEvent>,
 ) {
     debug!("Starting WebRtcSocket message loop");
 
     let (requests_sender, requests_receiver) = futures_channel::mpsc::unbounded::<PeerRequest>();
     let (events_sender, events_receiver) = futures_channel::mpsc::unbounded::<PeerEvent>();
 
-    let signalling_loop_fut =
-        signalling_loop(config.room_url.clone(), requests_receiver, events_sender);
+    let signalling_loop_fut = signalling_loop(
+        config.room_url.clone(),
+        requests_receiver,
+        events_sender,
+        room_events_tx,
+    );
 
     let message_loop_fut = message_loop(
         id,
@@ -3,12 +3,13 @@ use futures::{pin_mut, FutureExt, SinkExt, StreamExt};
 use futures_util::select;
 use log::{debug, warn};
 
-use crate::webrtc_socket::messages::{PeerEvent, PeerRequest};
+use crate::webrtc_socket::messages::{PeerEvent, PeerRequest, RoomEvent};
 
 pub async fn signalling_loop(
     room_url: String,
     mut requests_receiver: futures_channel::mpsc::UnboundedReceiver<PeerRequest>,
     events_sender: futures_channel::mpsc::UnboundedSender<PeerEvent>,
+    room_events_sender: futures_channel::mpsc::UnboundedSender<RoomEvent>,
 ) {
     debug!("Signalling loop started");
     let (mut wsio, _response) = connect_async(&room_url)
@@ -32,6 +33,10 @@ pub async fn signalling_loop(
                 match message {
                     Some(Ok(Message::Text(message))) => {
                         debug!("{}", message);
+                        if let Ok(event) = serde_json::from_str::<RoomEvent>(&message) {
+                            room_events_sender.unbounded_send(event).unwrap();
+                            continue;
+                        }
                         let event: PeerEvent = serde_json::from_str(&message)
                             .unwrap_or_else(|err| panic!("couldn't parse peer event: {}.\nEvent: {}", err, message));
                         events_sender.unbounded_send(event).unwrap();
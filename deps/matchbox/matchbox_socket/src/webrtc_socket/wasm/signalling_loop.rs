@@ -8,6 +8,7 @@ pub async fn signalling_loop(
     room_url: String,
     mut requests_receiver: futures_channel::mpsc::UnboundedReceiver<PeerRequest>,
     events_sender: futures_channel::mpsc::UnboundedSender<PeerEvent>,
+    room_events_sender: futures_channel::mpsc::UnboundedSender<RoomEvent>,
 ) {
     let (_ws, wsio) = WsMeta::connect(&room_url, None)
         .await
@@ -27,6 +28,10 @@ pub async fn signalling_loop(
                 match message {
                     Some(WsMessage::Text(message)) => {
                         debug!("{}", message);
+                        if let Ok(event) = serde_json::from_str::<RoomEvent>(&message) {
+                            room_events_sender.unbounded_send(event).unwrap();
+                            continue;
+                        }
                         let event: PeerEvent = serde_json::from_str(&message)
                             .unwrap_or_else(|_| panic!("couldn't parse peer event {}", message));
                         events_sender.unbounded_send(event).unwrap();
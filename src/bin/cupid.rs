@@ -0,0 +1,206 @@
+//Cupid, the matchmaking server for native matches, run with `cargo run --no-default-features --bin cupid`
+//
+//Clients connect over TCP and ask for a match, the first one waits and the next one is paired with it
+//Or one of them creates a private room, gets a short code back, and waits for whoever joins with that code
+//Each is told the address Cupid saw the other connect from, which GGRS player slot is theirs and who they are playing,
+//the clients then bind their UDP socket to the same local port they used here and talk to each other directly
+//The protocol is in src/cupid.rs
+extern crate structopt;
+
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+use storm_fighting::*;
+
+//A client gets this long to say hello and ask for a match, so one that never does can not stall everyone else
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+//How long a room that has been filled keeps turning people away with "room full" before its code is forgotten
+const FILLED_ROOM_MEMORY: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "cupid", about = "Pairs up Storm Fighting players for native matches.")]
+struct Opt {
+    #[structopt(short, long, default_value = "0.0.0.0", help = "Address to listen on")]
+    address: String,
+    #[structopt(short, long, default_value = "7878")]
+    port: u16
+}
+
+//Someone who has asked for a match, kept around while they wait on a second player
+struct WaitingClient {
+    stream: TcpStream,
+    address: SocketAddr,
+    profile: PlayerProfile
+}
+
+//Reads the hello, the profile and the request, anyone on a different protocol version is turned away
+fn handshake(stream: &mut TcpStream) -> Result<(PlayerProfile, MatchRequest), String> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT)).map_err(|x| x.to_string())?;
+    let hello: CupidHello = read_message(stream).map_err(|x| format!("bad hello: {}", x))?;
+    if hello.protocol_version != CUPID_PROTOCOL_VERSION {
+        let reason = format!("this Cupid speaks protocol {} but the game speaks {}, update the game", CUPID_PROTOCOL_VERSION, hello.protocol_version);
+        let _ = write_message(stream, &CupidResponse::Rejected { reason: reason.clone() });
+        //Let the client finish sending its request before hanging up, closing with it unread resets the connection
+        //and the client would never get to read why it was turned away
+        let _ = stream.shutdown(Shutdown::Write);
+        let _ = std::io::copy(stream, &mut std::io::sink());
+        return Err(reason);
+    }
+
+    let profile: PlayerProfile = read_message(stream).map_err(|x| format!("bad profile: {}", x))?;
+    let request: MatchRequest = read_message(stream).map_err(|x| format!("bad request: {}", x))?;
+    stream.set_read_timeout(None).map_err(|x| x.to_string())?;
+    //The other player is shown this name, so it gets the same cleaning up here as it did in the game
+    return Ok((PlayerProfile::new(&profile.name, profile.pallete), request));
+}
+
+//A waiting client that gave up closes the connection, which only shows up when we go to read from it
+fn is_still_connected(stream: &TcpStream) -> bool {
+    if stream.set_nonblocking(true).is_err() {
+        return false;
+    }
+    let mut buffer = [0u8; 1];
+    let connected = match stream.peek(&mut buffer) {
+        Ok(0) => false,
+        Ok(_) => true,
+        Err(ref err) if err.kind() == ErrorKind::WouldBlock => true,
+        Err(_) => false
+    };
+    let _ = stream.set_nonblocking(false);
+    return connected;
+}
+
+//Every RandomState is keyed differently, which is all the randomness a room code needs
+fn random_u64() -> u64 {
+    let hasher: DefaultHasher = RandomState::new().build_hasher();
+    return hasher.finish();
+}
+
+//Tells the waiting client and the new one about each other, the waiting client gets slot 0 so whoever got here first is player one
+//Returns false if the waiting client has gone away, the new one has not been told anything in that case
+fn pair(waiting: &mut WaitingClient, client: &mut WaitingClient) -> bool {
+    let first = CupidResponse::Matched { peer_address: client.address, player_slot: 0, peer_profile: client.profile.clone() };
+    if is_still_connected(&waiting.stream) == false || write_message(&mut waiting.stream, &first).is_err() {
+        println!("{} stopped waiting", waiting.address);
+        return false;
+    }
+
+    let second = CupidResponse::Matched { peer_address: waiting.address, player_slot: 1, peer_profile: waiting.profile.clone() };
+    if let Err(e) = write_message(&mut client.stream, &second) {
+        println!("Failed to tell {} about their match {:?}", client.address, e);
+    }
+    println!("Matched {} ({}) with {} ({})", waiting.address, waiting.profile.name, client.address, client.profile.name);
+    return true;
+}
+
+fn reject(client: &mut WaitingClient, reason: &str) {
+    println!("Turned away {}: {}", client.address, reason);
+    let _ = write_message(&mut client.stream, &CupidResponse::Rejected { reason: String::from(reason) });
+}
+
+struct Cupid {
+    waiting: Option<WaitingClient>, //For FindMatch, whoever asked first
+    rooms: HashMap<String, WaitingClient>, //Private rooms with their creator waiting in them
+    filled_rooms: HashMap<String, Instant> //Private rooms that already have two players, and when they filled up
+}
+
+impl Cupid {
+    fn new() -> Cupid {
+        Cupid {
+            waiting: None,
+            rooms: HashMap::new(),
+            filled_rooms: HashMap::new()
+        }
+    }
+
+    fn find_match(&mut self, mut client: WaitingClient) {
+        if let Some(mut waiting_client) = self.waiting.take() {
+            if pair(&mut waiting_client, &mut client) {
+                return;
+            }
+        }
+
+        if write_message(&mut client.stream, &CupidResponse::Waiting).is_ok() {
+            println!("{} is waiting for a match", client.address);
+            self.waiting = Some(client);
+        }
+    }
+
+    fn create_room(&mut self, mut client: WaitingClient) {
+        self.rooms.retain(|_, creator| is_still_connected(&creator.stream));
+        let mut code = room_code_from_random(random_u64());
+        while self.rooms.contains_key(&code) || self.filled_rooms.contains_key(&code) {
+            code = room_code_from_random(random_u64());
+        }
+
+        if write_message(&mut client.stream, &CupidResponse::RoomCreated { code: code.clone() }).is_ok() {
+            println!("{} created room {}", client.address, code);
+            self.rooms.insert(code, client);
+        }
+    }
+
+    fn join_room(&mut self, mut client: WaitingClient, code: &str) {
+        let code = normalize_room_code(code);
+        self.filled_rooms.retain(|_, filled_at| filled_at.elapsed() < FILLED_ROOM_MEMORY);
+
+        if let Some(mut creator) = self.rooms.remove(&code) {
+            if pair(&mut creator, &mut client) {
+                self.filled_rooms.insert(code, Instant::now());
+                return;
+            }
+            //The creator gave up on their room, so as far as anyone joining is concerned it is gone
+            reject(&mut client, "no such room");
+        }
+        else if self.filled_rooms.contains_key(&code) {
+            reject(&mut client, "room full");
+        }
+        else {
+            reject(&mut client, "no such room");
+        }
+    }
+}
+
+fn main() {
+    let opt = Opt::from_args();
+    let listener = match TcpListener::bind((opt.address.as_str(), opt.port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Can not listen on {}:{}: {}", opt.address, opt.port, e);
+            std::process::exit(1);
+        }
+    };
+    println!("Cupid listening on {}:{}, protocol version {}", opt.address, opt.port, CUPID_PROTOCOL_VERSION);
+
+    let mut cupid = Cupid::new();
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                println!("Failed to accept a connection {:?}", e);
+                continue;
+            }
+        };
+        let address = match stream.peer_addr() {
+            Ok(address) => address,
+            Err(_) => continue
+        };
+
+        let (profile, request) = match handshake(&mut stream) {
+            Ok(handshake) => handshake,
+            Err(e) => {
+                println!("Turned away {}: {}", address, e);
+                continue;
+            }
+        };
+        let client = WaitingClient { stream, address, profile };
+        match request {
+            MatchRequest::FindMatch => cupid.find_match(client),
+            MatchRequest::CreateRoom => cupid.create_room(client),
+            MatchRequest::JoinRoom { code } => cupid.join_room(client, &code)
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//Golden replay regression, run with `cargo run --no-default-features --bin replay_regression -- <folder>`
+//
+//Every .replay file in the folder is re-simulated headlessly and the state hash of every frame is compared
+//against the golden file sitting next to it, <name>.golden, so any change to how a recorded match plays out shows up
+//The goldens are checksum streams, a failing replay can go straight into `round_diff bisect` with its golden
+//
+//After an intentional gameplay change, run it again with --bless to rewrite the goldens from the current build
+extern crate structopt;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+use storm_fighting::*;
+
+const REPLAY_EXTENSION: &str = "replay";
+const GOLDEN_EXTENSION: &str = "golden";
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "replay_regression", about = "Checks that recorded replays still play out frame for frame the same.")]
+struct Opt {
+    #[structopt(parse(from_os_str), help = "Folder holding the .replay files and their .golden files")]
+    folder: PathBuf,
+    #[structopt(short, long, parse(from_os_str), default_value = "resources/ryu_character_sheet.json")]
+    sheet: PathBuf,
+    #[structopt(short, long, help = "Rewrite every golden from the current build instead of checking against them")]
+    bless: bool
+}
+
+enum Outcome {
+    Passed,
+    Failed(String),
+    Blessed
+}
+
+fn load_gameplay_config(sheet: &Path) -> Result<GameplayConfig, String> {
+    let text = fs::read_to_string(sheet).map_err(|x| format!("{}: {}", sheet.display(), x))?;
+    let character_sheet: CharacterSheet = serde_json::from_str(&text).map_err(|x| format!("{}: {}", sheet.display(), x))?;
+    return GameplayConfig::new_from_sheet(character_sheet).map_err(|x| format!("{}: {}", sheet.display(), x));
+}
+
+//Sorted so the report comes out in the same order every run
+fn find_replays(folder: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(folder).map_err(|x| format!("{}: {}", folder.display(), x))?;
+    let mut replays = vec![];
+    for entry in entries {
+        let path = entry.map_err(|x| format!("{}: {}", folder.display(), x))?.path();
+        if path.extension().map_or(false, |x| x == REPLAY_EXTENSION) {
+            replays.push(path);
+        }
+    }
+    replays.sort();
+    return Ok(replays);
+}
+
+//The first frame that is not the same, or why the two streams can not be lined up
+fn compare(checksums: &[u64], golden: &[(usize, u64)]) -> Option<String> {
+    for (frame, golden_checksum) in golden.iter() {
+        if *frame == 0 || *frame > checksums.len() {
+            return Some(format!("golden has frame {} but the replay is {} frames", frame, checksums.len()));
+        }
+        if checksums[frame - 1] != *golden_checksum {
+            return Some(format!("frame {} expected {:016x} got {:016x}", frame, golden_checksum, checksums[frame - 1]));
+        }
+    }
+    if golden.len() != checksums.len() {
+        return Some(format!("golden covers {} frames but the replay is {} frames", golden.len(), checksums.len()));
+    }
+    return None;
+}
+
+fn run_replay(path: &Path, gameplay_config: &GameplayConfig, bless: bool) -> Result<Outcome, String> {
+    let bytes = fs::read(path).map_err(|x| format!("{}: {}", path.display(), x))?;
+    let replay: Replay = bincode::deserialize(&bytes).map_err(|x| format!("{}: {}", path.display(), x))?;
+    //Not an error, changing the sheet is exactly what this is here to catch
+    if replay.check_config(gameplay_config).is_err() {
+        println!("    note: {} was recorded with config {:016x}, running with {:016x}", path.display(), replay.config_hash, gameplay_config.config_hash);
+    }
+
+    let checksums = replay.checksums(gameplay_config);
+    let golden_path = path.with_extension(GOLDEN_EXTENSION);
+    if bless {
+        let header = format!("# {} frames, game version {}, config {:016x}\n", checksums.len(), GAME_VERSION, gameplay_config.config_hash);
+        fs::write(&golden_path, header + &format_checksum_stream(&checksums)).map_err(|x| format!("{}: {}", golden_path.display(), x))?;
+        return Ok(Outcome::Blessed);
+    }
+
+    if golden_path.exists() == false {
+        return Ok(Outcome::Failed(format!("no golden at {}, run with --bless to make one", golden_path.display())));
+    }
+    let text = fs::read_to_string(&golden_path).map_err(|x| format!("{}: {}", golden_path.display(), x))?;
+    let golden = parse_checksum_stream(&text).map_err(|x| format!("{}: {}", golden_path.display(), x))?;
+    match compare(&checksums, &golden) {
+        Some(reason) => return Ok(Outcome::Failed(reason)),
+        None => return Ok(Outcome::Passed)
+    }
+}
+
+fn run(opt: &Opt) -> Result<bool, String> {
+    let gameplay_config = load_gameplay_config(&opt.sheet)?;
+    let replays = find_replays(&opt.folder)?;
+    if replays.is_empty() {
+        return Err(format!("no .{} files in {}", REPLAY_EXTENSION, opt.folder.display()));
+    }
+
+    let mut failed = 0;
+    for replay in replays.iter() {
+        match run_replay(replay, &gameplay_config, opt.bless)? {
+            Outcome::Passed => println!("ok      {}", replay.display()),
+            Outcome::Blessed => println!("blessed {}", replay.display()),
+            Outcome::Failed(reason) => {
+                println!("FAILED  {}: {}", replay.display(), reason);
+                failed += 1;
+            }
+        }
+    }
+
+    if opt.bless {
+        println!("Blessed {} goldens", replays.len());
+    }
+    else {
+        println!("{} passed, {} failed", replays.len() - failed, failed);
+    }
+    return Ok(failed == 0);
+}
+
+fn main() {
+    let opt = Opt::from_args();
+    match run(&opt) {
+        Ok(true) => {},
+        Ok(false) => std::process::exit(1),
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//Tools for tracking down desyncs, run with `cargo run --no-default-features --bin round_diff -- --help`
+//
+//  diff       prints every field that differs between two Round dumps, like the ones written on a desync
+//  checksums  re-simulates a replay and prints the state hash of every frame
+//  bisect     re-simulates a replay and reports the first frame that does not match a checksum stream
+//
+//Files ending in .json are read as JSON, anything else as bincode
+//A checksum stream is one "<frame> <checksum in hex>" pair per line, the frames do not need to be contiguous
+//Golden files from replay_regression are checksum streams too
+extern crate structopt;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use structopt::StructOpt;
+use storm_fighting::*;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "round_diff", about = "Compares Round dumps and replays to find where a desync started.")]
+enum Opt {
+    Diff {
+        #[structopt(parse(from_os_str))]
+        first: PathBuf,
+        #[structopt(parse(from_os_str))]
+        second: PathBuf
+    },
+    Checksums {
+        #[structopt(parse(from_os_str))]
+        replay: PathBuf,
+        #[structopt(short, long, parse(from_os_str), default_value = "resources/ryu_character_sheet.json")]
+        sheet: PathBuf
+    },
+    Bisect {
+        #[structopt(parse(from_os_str))]
+        replay: PathBuf,
+        #[structopt(parse(from_os_str))]
+        checksums: PathBuf,
+        #[structopt(short, long, parse(from_os_str), default_value = "resources/ryu_character_sheet.json")]
+        sheet: PathBuf,
+        #[structopt(short, long, parse(from_os_str), help = "Write the re-simulated Round at the divergent frame here")]
+        dump: Option<PathBuf>
+    }
+}
+
+fn load<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let bytes = fs::read(path).map_err(|x| format!("{}: {}", path.display(), x))?;
+    if path.extension().map_or(false, |x| x == "json") {
+        return serde_json::from_slice(&bytes).map_err(|x| format!("{}: {}", path.display(), x));
+    }
+    return bincode::deserialize(&bytes).map_err(|x| format!("{}: {}", path.display(), x));
+}
+
+fn load_gameplay_config(sheet: &Path) -> Result<GameplayConfig, String> {
+    let character_sheet: CharacterSheet = load(sheet)?;
+    return GameplayConfig::new_from_sheet(character_sheet).map_err(|x| format!("{}: {}", sheet.display(), x));
+}
+
+fn load_checksum_stream(path: &Path) -> Result<Vec<(usize, u64)>, String> {
+    let text = fs::read_to_string(path).map_err(|x| format!("{}: {}", path.display(), x))?;
+    return parse_checksum_stream(&text).map_err(|x| format!("{}: {}", path.display(), x));
+}
+
+//Walks both values together, recording the path to every leaf that is not the same
+fn diff_values(path: &str, first: &Value, second: &Value, differences: &mut Vec<String>) {
+    match (first, second) {
+        (Value::Object(first_fields), Value::Object(second_fields)) => {
+            for (name, first_value) in first_fields.iter() {
+                let field_path = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                match second_fields.get(name) {
+                    Some(second_value) => diff_values(&field_path, first_value, second_value, differences),
+                    None => differences.push(format!("{}: {} != <missing>", field_path, first_value))
+                }
+            }
+            for (name, second_value) in second_fields.iter() {
+                if first_fields.contains_key(name) == false {
+                    let field_path = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                    differences.push(format!("{}: <missing> != {}", field_path, second_value));
+                }
+            }
+        },
+        (Value::Array(first_items), Value::Array(second_items)) => {
+            if first_items.len() != second_items.len() {
+                differences.push(format!("{}.len(): {} != {}", path, first_items.len(), second_items.len()));
+            }
+            for index in 0..first_items.len().max(second_items.len()) {
+                let item_path = format!("{}[{}]", path, index);
+                match (first_items.get(index), second_items.get(index)) {
+                    (Some(first_item), Some(second_item)) => diff_values(&item_path, first_item, second_item, differences),
+                    (Some(first_item), None) => differences.push(format!("{}: {} != <missing>", item_path, first_item)),
+                    (None, Some(second_item)) => differences.push(format!("{}: <missing> != {}", item_path, second_item)),
+                    (None, None) => {}
+                }
+            }
+        },
+        _ => {
+            if first != second {
+                differences.push(format!("{}: {} != {}", path, first, second));
+            }
+        }
+    }
+}
+
+fn diff(first: &Path, second: &Path) -> Result<(), String> {
+    let first_round: Round = load(first)?;
+    let second_round: Round = load(second)?;
+    if first_round.state_hash() == second_round.state_hash() {
+        println!("The rounds match, state hash {:016x}", first_round.state_hash());
+        return Ok(());
+    }
+
+    //Fixed point values show up as their raw bits, divide by 65536 to get pixels
+    let first_value = serde_json::to_value(&first_round).map_err(|x| x.to_string())?;
+    let second_value = serde_json::to_value(&second_round).map_err(|x| x.to_string())?;
+    let mut differences = vec![];
+    diff_values("", &first_value, &second_value, &mut differences);
+
+    println!("{} differs from {} in {} fields", first.display(), second.display(), differences.len());
+    for difference in differences.iter() {
+        println!("    {}", difference);
+    }
+    return Ok(());
+}
+
+fn checksums(replay: &Path, sheet: &Path) -> Result<(), String> {
+    let replay: Replay = load(replay)?;
+    let gameplay_config = load_gameplay_config(sheet)?;
+    replay.check_config(&gameplay_config)?;
+    print!("{}", format_checksum_stream(&replay.checksums(&gameplay_config)));
+    return Ok(());
+}
+
+fn bisect(replay: &Path, checksums: &Path, sheet: &Path, dump: Option<PathBuf>) -> Result<(), String> {
+    let replay: Replay = load(replay)?;
+    let expected_checksums = load_checksum_stream(checksums)?;
+    let gameplay_config = load_gameplay_config(sheet)?;
+    replay.check_config(&gameplay_config)?;
+
+    let mut round = Replay::starting_round(&gameplay_config);
+    let mut expected = expected_checksums.iter().peekable();
+    for (index, inputs) in replay.inputs.iter().enumerate() {
+        round.advance_match(*inputs, &gameplay_config);
+        let frame = index + 1;
+        while let Some((expected_frame, expected_checksum)) = expected.peek() {
+            if *expected_frame > frame {
+                break;
+            }
+            expected.next();
+            if *expected_frame < frame {
+                continue;
+            }
+
+            let checksum = round.state_hash();
+            if checksum != *expected_checksum {
+                println!("First divergence on frame {}, expected {:016x} re-simulated {:016x}", frame, expected_checksum, checksum);
+                if let Some(dump) = dump {
+                    let json = serde_json::to_string_pretty(&round).map_err(|x| x.to_string())?;
+                    fs::write(&dump, json).map_err(|x| format!("{}: {}", dump.display(), x))?;
+                    println!("Wrote the re-simulated round to {}", dump.display());
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    if expected.peek().is_some() {
+        println!("No divergence in the {} frames of the replay, the checksum stream goes further", replay.inputs.len());
+    }
+    else {
+        println!("No divergence, all {} checksums match", expected_checksums.len());
+    }
+    return Ok(());
+}
+
+fn main() {
+    let result = match Opt::from_args() {
+        Opt::Diff { first, second } => diff(&first, &second),
+        Opt::Checksums { replay, sheet } => checksums(&replay, &sheet),
+        Opt::Bisect { replay, checksums, sheet, dump } => bisect(&replay, &checksums, &sheet, dump)
+    };
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,194 @@
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::SocketAddr;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+//The wire format between the game and Cupid, the matchmaking server in src/bin/cupid.rs
+//
+//Every message is a little endian u32 length followed by that many bytes of bincode
+//A client opens with a CupidHello, which never changes shape so that any two versions can at least read it,
+//then sends its PlayerProfile and a MatchRequest, Cupid answers with CupidResponses until it has found the client a match
+//Bump CUPID_PROTOCOL_VERSION whenever PlayerProfile, MatchRequest or CupidResponse change
+
+pub const CUPID_PORT: u16 = 7878;
+pub const CUPID_PROTOCOL_VERSION: u32 = 4;
+
+//Room codes get read out over voice chat, so they are short and leave out the letters and numbers that look alike
+pub const ROOM_CODE_LENGTH: usize = 5;
+const ROOM_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+//Names have to fit above a health bar
+pub const MAX_PLAYER_NAME_LENGTH: usize = 16;
+const DEFAULT_PLAYER_NAME: &str = "Player";
+
+//Nothing we send comes close, anything bigger is garbage or someone who is not speaking the protocol
+const MAX_MESSAGE_LENGTH: u32 = 64 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CupidHello {
+    pub protocol_version: u32
+}
+
+impl CupidHello {
+    pub fn new() -> CupidHello {
+        CupidHello {
+            protocol_version: CUPID_PROTOCOL_VERSION
+        }
+    }
+}
+
+impl Default for CupidHello {
+    fn default() -> CupidHello {
+        return CupidHello::new();
+    }
+}
+
+//Who a player is, swapped with the other player while matchmaking so both sides agree before the match starts
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerProfile {
+    pub name: String,
+    pub pallete: usize //Which pallete they picked, recorded into the replay
+}
+
+impl PlayerProfile {
+    //Names end up in the HUD and in the matchbox room URL, so only letters, numbers, spaces, - and _ are kept
+    pub fn new(name: &str, pallete: usize) -> PlayerProfile {
+        let name: String = name.chars()
+            .filter(|x| x.is_ascii_alphanumeric() || *x == ' ' || *x == '-' || *x == '_')
+            .take(MAX_PLAYER_NAME_LENGTH)
+            .collect();
+        let name = name.trim();
+        if name.is_empty() {
+            return PlayerProfile { name: String::from(DEFAULT_PLAYER_NAME), pallete };
+        }
+        return PlayerProfile { name: String::from(name), pallete };
+    }
+}
+
+//How a player wants to find their opponent, the same choices go through Cupid and through the matchbox server
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum MatchRequest {
+    FindMatch, //Pair me with whoever is waiting, or wait for the next person
+    CreateRoom, //Make a private room and give me its code to hand to a friend
+    JoinRoom {
+        code: String
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum CupidResponse {
+    Waiting, //Nobody to pair with yet, the connection stays open until someone shows up
+    RoomCreated {
+        code: String //Waiting in the room, the connection stays open until someone joins with this code
+    },
+    Matched {
+        peer_address: SocketAddr, //Where Cupid sees the other player connecting from, the GGRS socket binds the same port
+        player_slot: usize, //The GGRS player handle to use for ourselves, the peer gets the other one
+        peer_profile: PlayerProfile
+    },
+    Rejected {
+        reason: String //Wrong protocol version, "no such room" or "room full"
+    }
+}
+
+//Builds a room code out of some random bits, five characters only use 25 of them
+pub fn room_code_from_random(random: u64) -> String {
+    let mut random = random;
+    let mut code = String::with_capacity(ROOM_CODE_LENGTH);
+    for _ in 0..ROOM_CODE_LENGTH {
+        code.push(ROOM_CODE_ALPHABET[(random % ROOM_CODE_ALPHABET.len() as u64) as usize] as char);
+        random /= ROOM_CODE_ALPHABET.len() as u64;
+    }
+    return code;
+}
+
+//Codes are typed in by hand, so stray spaces and lower case are forgiven
+pub fn normalize_room_code(code: &str) -> String {
+    return code.trim().to_uppercase();
+}
+
+pub fn write_message<T: Serialize, W: Write>(writer: &mut W, message: &T) -> std::io::Result<()> {
+    let bytes = bincode::serialize(message).map_err(|x| Error::new(ErrorKind::InvalidData, x))?;
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    return writer.flush();
+}
+
+pub fn read_message<T: DeserializeOwned, R: Read>(reader: &mut R) -> std::io::Result<T> {
+    let mut length = [0u8; 4];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_le_bytes(length);
+    if length > MAX_MESSAGE_LENGTH {
+        return Err(Error::new(ErrorKind::InvalidData, format!("message of {} bytes is too long", length)));
+    }
+
+    let mut bytes = vec![0u8; length as usize];
+    reader.read_exact(&mut bytes)?;
+    return bincode::deserialize(&bytes).map_err(|x| Error::new(ErrorKind::InvalidData, x));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn messages_read_back_the_way_they_were_written() {
+        let matched = CupidResponse::Matched {
+            peer_address: "192.168.1.20:7001".parse().unwrap(),
+            player_slot: 1,
+            peer_profile: PlayerProfile::new("Ken", 1)
+        };
+        let mut bytes = vec![];
+        write_message(&mut bytes, &CupidHello::new()).unwrap();
+        write_message(&mut bytes, &matched).unwrap();
+
+        let mut reader = Cursor::new(bytes);
+        assert_eq!(read_message::<CupidHello, _>(&mut reader).unwrap(), CupidHello::new());
+        assert_eq!(read_message::<CupidResponse, _>(&mut reader).unwrap(), matched);
+        //Nothing left, so the next read runs out of bytes
+        assert_eq!(read_message::<CupidResponse, _>(&mut reader).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn messages_over_the_length_limit_are_rejected() {
+        let mut bytes = (MAX_MESSAGE_LENGTH + 1).to_le_bytes().to_vec();
+        bytes.resize(bytes.len() + MAX_MESSAGE_LENGTH as usize + 1, 0);
+        let error = read_message::<CupidResponse, _>(&mut Cursor::new(bytes)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn player_names_are_sanitised() {
+        assert_eq!(PlayerProfile::new("Ryu", 0).name, "Ryu");
+        assert_eq!(PlayerProfile::new("  Chun Li  ", 0).name, "Chun Li");
+        assert_eq!(PlayerProfile::new("<b>zangief</b>", 0).name, "bzangiefb");
+        assert_eq!(PlayerProfile::new("a-very_long name that goes on", 0).name, "a-very_long name");
+        assert_eq!(PlayerProfile::new("a-very_long name that goes on", 0).name.len(), MAX_PLAYER_NAME_LENGTH);
+        assert_eq!(PlayerProfile::new("", 0).name, DEFAULT_PLAYER_NAME);
+        assert_eq!(PlayerProfile::new("   ", 0).name, DEFAULT_PLAYER_NAME);
+        assert_eq!(PlayerProfile::new("!?&%", 0).name, DEFAULT_PLAYER_NAME);
+    }
+
+    #[test]
+    fn room_codes_only_use_the_alphabet() {
+        for random in [0, 1, 31, 32, 0xdeadbeef, u64::MAX].iter() {
+            let code = room_code_from_random(*random);
+            assert_eq!(code.len(), ROOM_CODE_LENGTH);
+            assert!(code.bytes().all(|x| ROOM_CODE_ALPHABET.contains(&x)), "{}", code);
+        }
+        assert_eq!(room_code_from_random(0), "AAAAA");
+        assert_eq!(room_code_from_random(1), "BAAAA");
+        assert_eq!(room_code_from_random(32), "ABAAA");
+        assert_eq!(room_code_from_random(0xdeadbeef), room_code_from_random(0xdeadbeef));
+        assert_ne!(room_code_from_random(0xdeadbeef), room_code_from_random(0xdeadbeee));
+    }
+
+    #[test]
+    fn typed_in_room_codes_are_normalized() {
+        assert_eq!(normalize_room_code("ABCDE"), "ABCDE");
+        assert_eq!(normalize_room_code("  abcde\n"), "ABCDE");
+        assert_eq!(normalize_room_code("x7k2q"), "X7K2Q");
+        assert_eq!(normalize_room_code(&room_code_from_random(12345).to_lowercase()), room_code_from_random(12345));
+    }
+}
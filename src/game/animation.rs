@@ -5,6 +5,7 @@ use storm::graphics::TextureFiltering;
 use storm::graphics::TextureSection;
 use storm::*;
 use crate::FightingApp;
+use super::*;
 
 // TODO: load these normally
 pub static BACKGROUND_CASTLE: &[u8] = include_bytes!("../../resources/background_castle.png");
@@ -12,147 +13,8 @@ pub static UI_BACKPLATE: &[u8] = include_bytes!("../../resources/health_and_time
 pub static GREYSCALE_HEALTH_BAR_GRADIANT: &[u8] = include_bytes!("../../resources/greyscale_health_bar.png");
 //pub static BUTTON: &[u8] = include_bytes!("../../resources/button.png");
 
-pub const FRAME_HEIGHT: u32 =  178;
-pub const FRAME_WIDTH: u32 =  290;
-
 pub const EFFECT_FRAME_WIDTH: u32 = 640;
 
-
-
-#[macro_export]
-macro_rules! animation_state_enum {
-    ( $( $x:ident ),* ) => {
-         #[derive(Eq, PartialEq, Hash, Debug, Serialize, Deserialize, Copy, Clone)]
-          pub enum AnimationState {
-            $(
-                $x,
-            )*
-          }
-
-          impl AnimationState {
-            pub fn from_string(value:&String) -> AnimationState {
-              $(
-                if value == stringify!($x) {
-                  return AnimationState::$x;
-                }
-              )*
-                      panic!("{:?} is an unknow animation state", value);
-            }
-
-            pub fn to_string(&self) -> String {
-              match *self {
-                $(
-                AnimationState::$x => {
-                  return String::from(stringify!($x));
-                }
-                )*
-              }
-            }
-          }
-    };
-}
-
-
-animation_state_enum!(    Idle,
-    ForwardRun,
-    BackwardRun,
-    LightAttack,
-    MediumAttack,
-    HeavyAttack,
-    LightHitRecovery,
-    Crouched,
-    Crouching,
-    Blocking,
-    LightCrouchAttack,
-    HeavyCrouchingAttack,
-    LightCrouchKick,
-    MediumCrouchKick,
-    HeavyCrouchKick,
-    LightKick,
-    MediumKick,
-    HeavyKick,
-    ForwardDash,
-    BackwardDash,
-    Special1,
-    Won,
-    Lost,
-    Jump,
-    Parry,
-    ForwardJump,
-    LightJumpingKick,
-    JumpingLightPunch,
-    Dizzie);
-
-#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone)]
-//A frame number based timer for sprites IE: Does not use delta timer/real time it is an monotonic timer
-pub struct SpriteTimer {
-    pub total_frames: u32,
-    pub current_frame: u32,
-    pub finished: bool,
-}
-
-impl SpriteTimer {
-    pub fn new(total_frames: u32) -> SpriteTimer {
-        SpriteTimer {
-            total_frames,
-            current_frame: 0,
-            finished: false,
-        }
-    }
-
-    //How you advance the timer 
-    pub fn tick(&mut self) {
-        self.finished = false;
-        self.current_frame += 1;
-        if self.current_frame == self.total_frames {
-            self.finished = true;
-            self.current_frame = 0;
-        }
-    }
-
-    //Call to see if this timer has finished it timer
-    pub fn finished(&mut self) -> bool {
-        self.finished
-    }
-
-    //Resets the state of the timer, but not total_frames
-    pub fn reset(&mut self) {
-        self.current_frame = 0;
-        self.finished = false;
-}
-}
-
-#[derive(Serialize, Deserialize, Clone)]
-//A data structure to handle the concept of an animation
-pub struct AnimationConfig {
-    pub sprite_timer: SpriteTimer, //The number of monotonic frames a single frame will take
-    pub current_frame: u32, //The current frame we are on
-    pub frame_times: Vec<u8>
-}
-
-impl AnimationConfig {
-    pub fn new(frame_times: Vec<u8>) -> AnimationConfig {
-        AnimationConfig {
-            sprite_timer: SpriteTimer::new(frame_times[0] as u32),
-            current_frame: 0,
-            frame_times
-        }
-    }
-
-    pub fn advance_to_next_frame(&mut self) {
-        self.sprite_timer = SpriteTimer::new(self.frame_times[self.current_frame as usize] as u32);
-    }
-
-    pub fn reset(&mut self) {
-        self.current_frame = 0;
-        self.sprite_timer.reset();
-    }
-
-    pub fn is_done(&self) -> bool {
-        return self.current_frame == self.frame_times.len() as u32;
-    }
-}
-
 pub struct AnimationTextureLibrary {
     pub animations: HashMap<AnimationState, Texture>,
 }
@@ -0,0 +1,98 @@
+use ggrs::PlayerType;
+
+use std::net::{Shutdown, TcpStream};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use super::*;
+
+//Asks Cupid for a match and then plays it over UDP, the protocol is in src/cupid.rs
+//
+//Cupid can keep us waiting for as long as it takes someone to show up, so the talking to it happens on
+//its own thread and the window keeps drawing in the meantime
+pub struct CupidTransport {
+    matched: Receiver<Result<(u16, Vec<MatchPlayer>), String>>, //The local port Cupid saw us on and who we are playing
+    udp: Option<UdpTransport> //Bound once Cupid has found us a match
+}
+
+impl CupidTransport {
+    pub fn new(cupid_ip_addr: &str, match_request: MatchRequest, profile: PlayerProfile) -> CupidTransport {
+        let cupid_socket = format!("{}:{}", cupid_ip_addr, CUPID_PORT);
+        let (sender, matched) = channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(find_match(&cupid_socket, &match_request, profile));
+        });
+
+        CupidTransport {
+            matched,
+            udp: None
+        }
+    }
+}
+
+fn find_match(cupid_socket: &str, match_request: &MatchRequest, profile: PlayerProfile) -> Result<(u16, Vec<MatchPlayer>), String> {
+    let mut stream = TcpStream::connect(cupid_socket).map_err(|x| format!("can not reach Cupid at {}: {}", cupid_socket, x))?;
+    write_message(&mut stream, &CupidHello::new()).map_err(|x| x.to_string())?;
+    write_message(&mut stream, &profile).map_err(|x| x.to_string())?;
+    write_message(&mut stream, match_request).map_err(|x| x.to_string())?;
+
+    //Cupid keeps the connection open until it has someone for us
+    loop {
+        match read_message::<CupidResponse, _>(&mut stream).map_err(|x| x.to_string())? {
+            CupidResponse::Waiting => {
+                println!("Waiting for another player");
+            },
+            CupidResponse::RoomCreated { code } => {
+                println!("Created room {}, give this code to the other player", code);
+            },
+            CupidResponse::Matched { peer_address, player_slot, peer_profile } => {
+                println!("Matched with {} at {}", peer_profile.name, peer_address);
+                let local_port = stream.local_addr().map_err(|x| x.to_string())?.port();
+                //Now that we have the info kill our connection
+                let _ = stream.shutdown(Shutdown::Both);
+
+                let mut players = vec![];
+                for handle in 0..2 {
+                    if handle == player_slot {
+                        players.push(MatchPlayer { player_type: PlayerType::Local, profile: profile.clone() });
+                    }
+                    else {
+                        players.push(MatchPlayer { player_type: PlayerType::Remote(PeerAddress::Udp(peer_address)), profile: peer_profile.clone() });
+                    }
+                }
+                return Ok((local_port, players));
+            },
+            CupidResponse::Rejected { reason } => {
+                return Err(format!("Cupid turned us away: {}", reason));
+            }
+        }
+    }
+}
+
+impl Transport for CupidTransport {
+    fn poll_players(&mut self) -> Result<Option<Vec<MatchPlayer>>, String> {
+        let (local_port, players) = match self.matched.try_recv() {
+            Ok(Ok(matched)) => matched,
+            Ok(Err(reason)) => return Err(reason),
+            Err(TryRecvError::Empty) => return Ok(None),
+            Err(TryRecvError::Disconnected) => return Err(String::from("lost the thread talking to Cupid"))
+        };
+
+        //The UDP socket binds the same port Cupid saw us on, that is the address it gave the other player
+        let mut udp = UdpTransport::bind_to_port(local_port, players).map_err(|x| format!("can not bind port {}: {}", local_port, x))?;
+        let players = udp.poll_players();
+        self.udp = Some(udp);
+        return players;
+    }
+
+    fn send_to(&mut self, packet: &[u8], address: &PeerAddress) {
+        if let Some(udp) = self.udp.as_mut() {
+            udp.send_to(packet, address);
+        }
+    }
+
+    fn receive_all(&mut self) -> Result<Vec<(PeerAddress, Vec<u8>)>, String> {
+        match self.udp.as_mut() {
+            Some(udp) => return udp.receive_all(),
+            None => return Ok(vec![])
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use ggrs::Frame;
+use super::*;
+
+//How many checksums we hold onto waiting for the other side's copy, the peer sends theirs
+//as soon as the frame is confirmed so they should never be more than a couple apart
+const CHECKSUM_HISTORY_LENGTH: usize = 16;
+
+#[cfg(not(target_arch = "wasm32"))]
+const DESYNC_DUMP_FOLDER: &str = "./desyncs";
+
+//Sent to the peer for every confirmed frame that lands on a CHECKSUM_PERIOD
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChecksumReport {
+    pub frame: Frame,
+    pub checksum: u64
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct DesyncEvent {
+    pub frame: Frame,
+    pub local_checksum: u64,
+    pub remote_checksum: u64
+}
+
+//Pairs up our confirmed checksums with the peer's, either one can show up first
+//The local Round is kept with its checksum so that there is something to dump if they do not match
+//Ours are kept after they have been compared too, GGRS can report a desync on a frame we have already checked
+pub struct DesyncDetector {
+    local: VecDeque<(ChecksumReport, Round)>,
+    remote: VecDeque<ChecksumReport>
+}
+
+impl DesyncDetector {
+    pub fn new() -> DesyncDetector {
+        DesyncDetector {
+            local: VecDeque::new(),
+            remote: VecDeque::new()
+        }
+    }
+
+    pub fn add_local(&mut self, report: ChecksumReport, round: Round) -> Option<(DesyncEvent, Round)> {
+        if self.local.len() == CHECKSUM_HISTORY_LENGTH {
+            self.local.pop_front();
+        }
+        self.local.push_back((report, round));
+
+        if let Some(index) = self.remote.iter().position(|x| x.frame == report.frame) {
+            let remote = self.remote.remove(index).unwrap();
+            return DesyncDetector::compare(report, remote, round);
+        }
+        return None;
+    }
+
+    pub fn add_remote(&mut self, report: ChecksumReport) -> Option<(DesyncEvent, Round)> {
+        if let Some((local, round)) = self.local.iter().find(|x| x.0.frame == report.frame) {
+            return DesyncDetector::compare(*local, report, *round);
+        }
+
+        if self.remote.len() == CHECKSUM_HISTORY_LENGTH {
+            self.remote.pop_front();
+        }
+        self.remote.push_back(report);
+        return None;
+    }
+
+    //Our copy of the Round on a frame we sent a checksum for, if it is still recent enough to have been kept
+    pub fn local_round(&self, frame: Frame) -> Option<Round> {
+        return self.local.iter().find(|x| x.0.frame == frame).map(|x| x.1);
+    }
+
+    fn compare(local: ChecksumReport, remote: ChecksumReport, round: Round) -> Option<(DesyncEvent, Round)> {
+        if local.checksum == remote.checksum {
+            return None;
+        }
+
+        let event = DesyncEvent {
+            frame: local.frame,
+            local_checksum: local.checksum,
+            remote_checksum: remote.checksum
+        };
+        return Some((event, round));
+    }
+}
+
+impl Default for DesyncDetector {
+    fn default() -> DesyncDetector {
+        return DesyncDetector::new();
+    }
+}
+
+//Writes the Round as we had it on the desynced frame, both players end up with one of these to compare
+#[cfg(not(target_arch = "wasm32"))]
+pub fn dump_desynced_round(round: &Round, event: &DesyncEvent, local_handle: usize) -> std::io::Result<String> {
+    std::fs::create_dir_all(DESYNC_DUMP_FOLDER)?;
+    let path = format!("{}/desync_frame_{}_player_{}.json", DESYNC_DUMP_FOLDER, event.frame, local_handle + 1);
+    let json = serde_json::to_string_pretty(round).map_err(|x| std::io::Error::new(std::io::ErrorKind::Other, x))?;
+    std::fs::write(&path, json)?;
+    return Ok(path);
+}
+
+//There is no file system on the web, so the dump goes to the console instead
+#[cfg(target_arch = "wasm32")]
+pub fn dump_desynced_round(round: &Round, event: &DesyncEvent, local_handle: usize) -> std::io::Result<String> {
+    let json = serde_json::to_string(round).map_err(|x| std::io::Error::new(std::io::ErrorKind::Other, x))?;
+    println!("desync frame {} player {}: {}", event.frame, local_handle + 1, json);
+    return Ok(String::from("console"));
+}
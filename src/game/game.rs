@@ -1,10 +1,11 @@
 
 use core::panic;
 use core::time::Duration;
+use std::collections::BTreeMap;
 
 extern crate simplelog;
 
-use storm::cgmath::Vector3;
+use storm::cgmath::{Vector2, Vector3};
 use storm::color::RGBA8;
 
 use storm::graphics::*;
@@ -17,68 +18,77 @@ use storm::fontdue::Font;
 use storm::graphics::shaders::text::{Text};
 use ggrs::{GGRSError};
 
-use hashbrown::HashMap;
-
-use ggrs::{Frame, GGRSRequest, GameStateCell, PlayerHandle, NULL_FRAME, InputStatus};
+use ggrs::{Config, Frame, GGRSEvent, GGRSRequest, GameStateCell, PlayerHandle, NULL_FRAME, InputStatus};
 use storm::math::OrthographicCamera;
 
 use super::*;
-use super::character::AnimationStateForCharacterState;
 
 pub const CHECKSUM_PERIOD: i32 = 100;
+//How long at the start of every round the banner saying which side you are on stays up
+pub const ROUND_INTRO_FRAMES: u32 = 2 * 60;
 
-use crate::*;
-
-/// computes the fletcher16 checksum, copied from wikipedia: <https://en.wikipedia.org/wiki/Fletcher%27s_checksum>
-fn fletcher16(data: &[u8]) -> u16 {
-    let mut sum1: u16 = 0;
-    let mut sum2: u16 = 0;
-
-    for index in 0..data.len() {
-        sum1 = (sum1 + data[index] as u16) % 255;
-        sum2 = (sum2 + sum1) % 255;
-    }
+#[cfg(not(target_arch = "wasm32"))]
+const REPLAY_FOLDER: &str = "./replays";
 
-    (sum2 << 8) | sum1
-}
+use crate::*;
 
+//The client side config, wraps the gameplay config with everything that needs a GPU
 pub struct GameConfig {
-    pub collision_library: CollisionLibrary,
-    pub combo_library: ComboLibrary,
+    pub gameplay_config: GameplayConfig,
     pub animation_library: AnimationTextureLibrary,
-    pub animation_for_character_state_library: HashMap<CharacterState, AnimationStateForCharacterState>,
-    pub animation_configs: HashMap<AnimationState, AnimationConfig>,
-    pub character_sheet: CharacterSheet,
-    pub pallete: [cgmath::Vector3<f32>; 256]
+    pub pallete: [cgmath::Vector3<f32>; 256],
+    pub pallete_choice: usize //Which of the palletes from read_palletes the pallete is
 }
 
 impl GameConfig {
-    pub fn new(collision_library: CollisionLibrary, 
-               combo_library: ComboLibrary,
+    pub fn new(gameplay_config: GameplayConfig,
                animation_library: AnimationTextureLibrary,
-               animation_for_character_state_library: HashMap<CharacterState, AnimationStateForCharacterState>,
-               animation_configs: HashMap<AnimationState, AnimationConfig>,
-               character_sheet: CharacterSheet,
-               pallete: [cgmath::Vector3<f32>; 256]) -> GameConfig {
+               pallete: [cgmath::Vector3<f32>; 256],
+               pallete_choice: usize) -> GameConfig {
         GameConfig {
-            collision_library,
-            combo_library,
+            gameplay_config,
             animation_library,
-            animation_for_character_state_library,
-            animation_configs,
-            character_sheet,
-            pallete
+            pallete,
+            pallete_choice
         }
     }
 }
 
+#[derive(Debug)]
+pub struct GGRSConfig {
+
+}
+
+
+impl Config for GGRSConfig {
+    type Input = NetInput;
+    type State = Round;
+    type Address = PeerAddress;
+}
+
+//What is driving the current_round forward
+pub enum GameMode<'a> {
+    Online(Net<'a>), //A GGRS session against another player
+    Replay(ReplayPlayer), //Watching a recorded match, nothing is sent over the network
+    SyncTest(SyncTest), //Checking the simulation is deterministic by rolling back every frame, no network
+    LocalVersus(LocalVersus) //Two players on one keyboard, no network and nothing to roll back
+}
+
 pub struct Game<'a> {
     pub current_round: Round,
     pub local_input: Input,
     pub last_checksum: (Frame, u64),
     pub periodic_checksum: (Frame, u64),
+    pub session_frame: Frame, //The GGRS frame the current_round is on, unlike the round frame it never resets
+    pub desync_detector: DesyncDetector,
+    pub desyncs: Vec<DesyncEvent>,
+    pub unconfirmed_checksums: BTreeMap<Frame, (u64, Round)>, //The CHECKSUM_PERIOD frames GGRS could still roll back, sent once they are confirmed
+    pub frames_to_skip: u32, //Set by GGRS when we are running ahead of the other player
     pub game_config: GameConfig,
-    pub net: Net<'a>,
+    pub mode: GameMode<'a>,
+    pub replay: Replay, //Every confirmed frame of an online match, written out when the game closes
+    pub unconfirmed_inputs: BTreeMap<Frame, [NetInput; 2]>, //What each frame GGRS could still roll back was last played with
+    pub playback_controls: PlaybackControls,
     pub ui: UI,
 
     pub character_1_sprites: [PalleteSprite;1],
@@ -102,11 +112,28 @@ pub struct Game<'a> {
 
 impl<'a> Game<'a> {
     
-    pub fn load_game_with_config(ctx: &mut Context<FightingApp>, mut game_config: GameConfig, cupid_ip_addr: &str) -> Game<'a> {
+    pub fn load_game_with_config(ctx: &mut Context<FightingApp>, game_config: GameConfig, matchmaker: Matchmaker, match_request: MatchRequest, profile: PlayerProfile) -> Result<Game<'a>, String> {
+        let net = Net::launch_session(matchmaker, match_request, profile)?;
+        return Ok(Game::new(ctx, game_config, GameMode::Online(net)));
+    }
 
-        
-        let mut current_round = Round::new_with_animation_lib(&mut game_config.animation_configs);
-        let net = Net::launch_session(cupid_ip_addr);
+    pub fn load_replay(ctx: &mut Context<FightingApp>, game_config: GameConfig, replay_player: ReplayPlayer) -> Game<'a> {
+        return Game::new(ctx, game_config, GameMode::Replay(replay_player));
+    }
+
+    pub fn load_sync_test(ctx: &mut Context<FightingApp>, game_config: GameConfig, sync_test: SyncTest) -> Game<'a> {
+        return Game::new(ctx, game_config, GameMode::SyncTest(sync_test));
+    }
+
+    pub fn load_local_versus(ctx: &mut Context<FightingApp>, game_config: GameConfig) -> Game<'a> {
+        return Game::new(ctx, game_config, GameMode::LocalVersus(LocalVersus::new()));
+    }
+
+    fn new(ctx: &mut Context<FightingApp>, game_config: GameConfig, mode: GameMode<'a>) -> Game<'a> {
+        //Every match starts on the same Round so that replays can rebuild it
+        let mut current_round = Replay::starting_round(&game_config.gameplay_config);
+        //Online the other player's pallete is only known once matchmaking is done, online_update fills it in then
+        let replay = Replay::new(&game_config.gameplay_config, [game_config.pallete_choice; 2]);
 
         let (background_sprite, background_sprite_pass) = setup_background(ctx);
         let sprite_shader = SpriteShader::new(ctx);
@@ -133,8 +160,16 @@ impl<'a> Game<'a> {
             local_input: Input::new(),
             last_checksum: (NULL_FRAME, 0),
             periodic_checksum: (NULL_FRAME, 0),
+            session_frame: 0,
+            desync_detector: DesyncDetector::new(),
+            desyncs: vec![],
+            unconfirmed_checksums: BTreeMap::new(),
+            frames_to_skip: 0,
             game_config,
-            net,
+            mode,
+            replay,
+            unconfirmed_inputs: BTreeMap::new(),
+            playback_controls: PlaybackControls::new(),
             ui,
             character_1_sprites: sprites_1,
             sprite_pass_1,            
@@ -157,66 +192,247 @@ impl<'a> Game<'a> {
 
 
     pub fn key_down(&mut self, keyboard_button: KeyboardButton) {
-        self.local_input.key_down(keyboard_button);
+        match &mut self.mode {
+            GameMode::Replay(_) => self.playback_controls.key_down(keyboard_button),
+            GameMode::LocalVersus(local_versus) => local_versus.key_down(keyboard_button),
+            _ => self.local_input.key_down(keyboard_button)
+        }
     }
 
     pub fn key_up(&mut self, keyboard_button: KeyboardButton) {
-        self.local_input.key_up(keyboard_button);
+        match &mut self.mode {
+            GameMode::Replay(_) => self.playback_controls.key_up(keyboard_button),
+            GameMode::LocalVersus(local_versus) => local_versus.key_up(keyboard_button),
+            _ => self.local_input.key_up(keyboard_button)
+        }
+    }
+
+    //Only called while online, the other modes have no network session
+    fn net(&mut self) -> &mut Net<'a> {
+        match &mut self.mode {
+            GameMode::Online(net) => net,
+            _ => panic!("There is no network session outside of an online game")
+        }
+    }
+
+    fn sync_test(&mut self) -> &mut SyncTest {
+        match &mut self.mode {
+            GameMode::SyncTest(sync_test) => sync_test,
+            _ => panic!("There is no sync test session outside of a sync test")
+        }
+    }
+
+    pub fn local_handle(&self) -> usize {
+        match &self.mode {
+            GameMode::Online(net) => net.local_handle,
+            _ => 0
+        }
+    }
+
+    //Which character is being played from this keyboard, only online matches have a single local player
+    pub fn you(&self) -> Option<usize> {
+        match &self.mode {
+            GameMode::Online(net) => Some(net.local_handle),
+            _ => None
+        }
+    }
+
+    //Online the names were swapped during matchmaking, everywhere else nobody has told us who is playing
+    pub fn player_name(&self, index: usize) -> String {
+        match &self.mode {
+            GameMode::Online(net) => net.player_profiles[index].name.clone(),
+            _ => format!("Player {}", index + 1)
+        }
+    }
+
+    //Why the online match or the sync test could not go on, the app goes back to the menu and shows it
+    pub fn failure(&self) -> Option<String> {
+        match &self.mode {
+            GameMode::Online(net) => match &net.state {
+                NetState::Failed(reason) => Some(reason.clone()),
+                _ => None
+            },
+            GameMode::SyncTest(sync_test) => sync_test.error.clone(),
+            _ => None
+        }
     }
 
     // deserialize gamestate to load and overwrite current gamestate
-    pub fn load_game_state(&mut self, cell: GameStateCell<Round>) {
+    pub fn load_game_state(&mut self, cell: GameStateCell<Round>, frame: Frame) {
         self.current_round = cell.load().expect("No data found.");
+        self.session_frame = frame;
     }
 
-    // serialize current gamestate, create a checksum
+    // save a copy of the current gamestate, create a checksum
     // creating a checksum here is only relevant for SyncTestSessions
     fn save_game_state(&mut self, cell: GameStateCell<Round>, frame: Frame) {
         // assert_eq!(self.game_state.frame, frame);
-        let buffer = bincode::serialize(&self.current_round).unwrap();
-        let checksum = fletcher16(&buffer) as u128;
+        let checksum = self.current_round.state_hash() as u128;
+
+        cell.save(frame, Some(self.current_round), Some(checksum));
+        self.session_frame = frame;
 
-        cell.save(frame, Some(self.current_round.clone()), Some(checksum));
+        //Online the state is saved every frame, and saved again whenever a rollback plays the frame over
+        //so the last save of a frame before it is confirmed is the one the peer will have too
+        if let GameMode::Online(_) = self.mode {
+            if frame > 0 && frame % CHECKSUM_PERIOD == 0 {
+                self.unconfirmed_checksums.insert(frame, (checksum as u64, self.current_round));
+            }
+        }
     }
     
     fn advance_frame(&mut self, inputs: Vec<(NetInput, InputStatus)>) {
         // advance the game state
-        self.current_round.advance(inputs, &mut self.game_config);
-        if self.current_round.round_done && self.current_round.reset_round_timer.finished() {
-            self.current_round = Round::default();
-        }
+        let frame = self.session_frame;
+        let inputs = [inputs[0].0, inputs[1].0];
+        self.current_round.advance_match(inputs, &self.game_config.gameplay_config);
+        self.session_frame += 1;
 
         // remember checksum to render it later
-        // it is very inefficient to serialize the gamestate here just for the checksum
-        let buffer = bincode::serialize(&self.current_round).unwrap();
-        let checksum = fletcher16(&buffer) as u64;
-        self.last_checksum = (self.current_round.frame, checksum);
-        if self.current_round.frame % CHECKSUM_PERIOD == 0 {
-            self.periodic_checksum = (self.current_round.frame, checksum);
+        let checksum = self.current_round.state_hash();
+        self.last_checksum = (self.session_frame, checksum);
+
+        // a sync test confirms every frame it re-simulates, so only online frames are recorded or sent to the peer
+        match self.mode {
+            GameMode::Online(_) => {},
+            _ => return
         }
+        //GGRS only plays a frame again if it guessed the inputs wrong, so most frames are never played with confirmed inputs
+        //Instead every frame is held onto, a rollback overwrites it, and it goes into the replay once GGRS has confirmed it
+        self.unconfirmed_inputs.insert(frame, inputs);
     }
 
-    pub fn update(&mut self, ctx: &mut Context<FightingApp>) {
+    //Writes out everything recorded so far, only real matches are recorded
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_replay(&self) {
+        match self.mode {
+            GameMode::Online(_) | GameMode::LocalVersus(_) => {},
+            _ => return
+        }
+        if self.replay.inputs.len() == 0 {
+            return;
+        }
+
+        let seconds = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|x| x.as_secs()).unwrap_or(0);
+        let path = format!("{}/replay_{}.replay", REPLAY_FOLDER, seconds);
+        let result = std::fs::create_dir_all(REPLAY_FOLDER)
+            .and_then(|_| std::fs::write(&path, bincode::serialize(&self.replay).unwrap()));
+        match result {
+            Ok(_) => println!("Saved the replay to {}", path),
+            Err(e) => println!("Failed to save the replay {:?}", e)
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn save_replay(&self) {
+    }
+
+    //Frames up to the confirmed frame will never be rolled back, so what they were last played with is what happened
+    fn record_confirmed_frames(&mut self) {
+        let confirmed_frame = self.net().confirmed_frame();
+        let still_unconfirmed = self.unconfirmed_inputs.split_off(&(confirmed_frame + 1));
+        for (_, inputs) in std::mem::replace(&mut self.unconfirmed_inputs, still_unconfirmed) {
+            self.replay.record(inputs);
+        }
+    }
 
+    //Checksums are only sent once GGRS has confirmed their frame, so both players always report the same frames
+    fn send_confirmed_checksums(&mut self) {
+        let confirmed_frame = self.net().confirmed_frame();
+        let still_unconfirmed = self.unconfirmed_checksums.split_off(&(confirmed_frame + 1));
+        for (frame, (checksum, round)) in std::mem::replace(&mut self.unconfirmed_checksums, still_unconfirmed) {
+            self.periodic_checksum = (frame, checksum);
 
+            let report = ChecksumReport {
+                frame,
+                checksum
+            };
+            self.net().send_checksum(report);
+            if let Some((event, round)) = self.desync_detector.add_local(report, round) {
+                self.report_desync(event, Some(&round));
+            }
+        }
+    }
+
+    fn report_desync(&mut self, event: DesyncEvent, round: Option<&Round>) {
+        println!("Desync on frame {}, local checksum {:016x} remote checksum {:016x}", event.frame, event.local_checksum, event.remote_checksum);
+        match round {
+            Some(round) => {
+                match dump_desynced_round(round, &event, self.local_handle()) {
+                    Ok(path) => println!("Wrote the local round to {}", path),
+                    Err(e) => println!("Failed to write the desynced round {:?}", e)
+                }
+            },
+            None => println!("No copy of the round from frame {} was kept, there is nothing to write out", event.frame)
+        }
+        self.desyncs.push(event);
+    }
+
+    pub fn handle_ggrs_event(&mut self, event: GGRSEvent<GGRSConfig>) {
+        match event {
+            GGRSEvent::WaitRecommendation { skip_frames } => {
+                self.frames_to_skip += skip_frames;
+            },
+            //GGRS's checksums are our state hashes too, so its desyncs are reported the same way as our own
+            GGRSEvent::DesyncDetected { frame, local_checksum, remote_checksum, .. } => {
+                let event = DesyncEvent {
+                    frame,
+                    local_checksum: local_checksum as u64,
+                    remote_checksum: remote_checksum as u64
+                };
+                let round = self.desync_detector.local_round(frame);
+                self.report_desync(event, round.as_ref());
+            },
+            _ => {
+                println!("GGRS event: {:?}", event);
+            }
+        }
+    }
+
+    pub fn update(&mut self, ctx: &mut Context<FightingApp>) {
         ctx.clear(ClearMode::new().with_color(RGBA8::BLUE).with_depth(0.0, DepthTest::Greater));
-        
-        self.net.tick();
-        match self.net.state {
-            NetState::Connecting => {
+
+        match self.mode {
+            GameMode::Online(_) => self.online_update(ctx),
+            GameMode::Replay(_) => self.replay_update(ctx),
+            GameMode::SyncTest(_) => self.sync_test_update(ctx),
+            GameMode::LocalVersus(_) => self.local_versus_update(ctx)
+        }
+    }
+
+    fn online_update(&mut self, ctx: &mut Context<FightingApp>) {
+        let was_connecting = matches!(self.net().state, NetState::Connecting);
+        self.net().tick();
+        match self.net().state {
+            NetState::Connecting | NetState::Failed(_) => {
                 return;
             },
             _ => {
 
             }
         }
+        if was_connecting {
+            //Everyone's profile came in with the match, so the replay can record each player's own pallete
+            let player_profiles = &self.net().player_profiles;
+            let palletes = [player_profiles[0].pallete, player_profiles[1].pallete];
+            self.replay.palletes = palletes;
+        }
 
+        for event in self.net().events() {
+            self.handle_ggrs_event(event);
+        }
 
-        if self.net.is_running() {
+        for report in self.net().received_checksums() {
+            if let Some((event, round)) = self.desync_detector.add_remote(report) {
+                self.report_desync(event, Some(&round));
+            }
+        }
+
+        if self.net().is_running() {
             // this is to keep ticks between clients synchronized.
             // if a client is ahead, it will run frames slightly slower to allow catching up
             let mut fps_delta = 1. / FPS;
-            if self.net.session.as_mut().unwrap().frames_ahead() > 0 {
+            if self.net().session.as_mut().unwrap().frames_ahead() > 0 {
                 fps_delta *= 1.1;
             }
 
@@ -224,243 +440,538 @@ impl<'a> Game<'a> {
             let delta = Instant::now().duration_since(self.last_update);
             self.accumulator = self.accumulator.saturating_add(delta);
             self.last_update = Instant::now();
-            
-
-
 
             // if enough time is accumulated, we run a frame
             while self.accumulator.as_secs_f64() > fps_delta {
                 // decrease accumulator
                 self.accumulator = self.accumulator.saturating_sub(Duration::from_secs_f64(fps_delta));
-                self.net.add_local_input(self.net.local_handle, self.local_input(self.net.local_handle));
-
-                
-                match self.net.session.as_mut().unwrap().advance_frame() {
-                    Ok(requests) => self.handle_requests(requests),
-                    Err(GGRSError::PredictionThreshold) => println!("Frame skipped"),
-                    Err(e) => panic!("{:?}", e),
-                }
-
-                //Update all of the sprites positions
-                //TODO: maybe use a is_dirty flag to update this only when we need to
-                self.sprite_pass_1.atlas = self.game_config.animation_library.get_atlas_for_animation(self.current_round.characters[0].animation_state);
-                
-                let frame = self.current_round.characters[0].get_current_animation_config();
-                if self.current_round.characters[0].screen_side == ScreenSide::Right {
-                    self.character_1_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[0].animation_state, frame.current_frame);
+                if self.frames_to_skip > 0 {
+                    self.frames_to_skip -= 1;
+                    continue;
                 }
-                else {
-                    self.character_1_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[0].animation_state, frame.current_frame).mirror_y();
-                }
-                
-                self.character_1_sprites[0].pos.x = self.current_round.characters[0].character_position.x;
-                self.character_1_sprites[0].pos.y = self.current_round.characters[0].character_position.y;
-
-                self.sprite_pass_2.atlas = self.game_config.animation_library.get_atlas_for_animation(self.current_round.characters[1].animation_state);
-                let frame = self.current_round.characters[1].get_current_animation_config();
-                if self.current_round.characters[0].screen_side == ScreenSide::Left {
-                    self.character_2_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[1].animation_state, frame.current_frame);
-                }
-                else {
-                    self.character_2_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[1].animation_state, frame.current_frame).mirror_y();
+                let local_handle = self.net().local_handle;
+                let local_input = self.local_input(local_handle);
+                if let Err(e) = self.net().add_local_input(local_handle, local_input) {
+                    self.net().fail(format!("GGRS would not take the local input: {:?}", e));
+                    return;
                 }
 
-                self.character_2_sprites[0].pos.x = self.current_round.characters[1].character_position.x;
-                self.character_2_sprites[0].pos.y = self.current_round.characters[1].character_position.y;
-
-                if self.current_round.projectiles.len() != self.projectile_sprites.len() {
-                    let diff = self.current_round.projectiles.len().abs_diff(self.projectile_sprites.len());
-                    if self.current_round.projectiles.len() > self.projectile_sprites.len() {
-                        //we need to add the number of new sprites
-                        for _ in 0..diff {
-                            let (fireball_sprite, mut fireball_render_pass) = setup_fireball(ctx);
-                            fireball_render_pass.atlas = self.fireball_texture.clone();
-                            self.projectile_sprites.push((fireball_sprite, fireball_render_pass))
-                        }
-                    }
-                    else {
-                        //remove the 
-                        self.projectile_sprites.truncate(self.projectile_sprites.len() - diff);
+                match self.net().session.as_mut().unwrap().advance_frame() {
+                    Ok(requests) => {
+                        self.handle_requests(requests);
+                        self.record_confirmed_frames();
+                        self.send_confirmed_checksums();
+                    },
+                    Err(GGRSError::PredictionThreshold) => println!("Frame skipped"),
+                    Err(e) => {
+                        self.net().fail(format!("GGRS could not advance the frame: {:?}", e));
+                        return;
                     }
                 }
 
-                            
-                for (index, projectile) in self.current_round.projectiles.iter().enumerate() {
-                    let left = projectile.timer.current_frame * FRAME_WIDTH;
-
-                    let test;
-                    match projectile.screen_side {
-                        ScreenSide::Left => {
-                            test = self.projectile_sprites[index].1.atlas.subsection(left, 0 + FRAME_WIDTH, 0, FRAME_HEIGHT).mirror_y();
-                            self.projectile_sprites[index].0[0].pos.x = projectile.position.x * X_SCALE as f32 ;//+ (FRAME_WIDTH as f32 / 2.0) * X_SCALE as f32;
-                        },
-                        ScreenSide::Right => {
-                            test = self.projectile_sprites[index].1.atlas.subsection(left, 0 + FRAME_WIDTH, 0, FRAME_HEIGHT);
-                            self.projectile_sprites[index].0[0].pos.x = projectile.position.x * X_SCALE as f32 - (FRAME_WIDTH as f32 / 2.0) * X_SCALE as f32;
-                        }
-                    }
-                    self.projectile_sprites[index].0[0].texture = test;
+                self.update_sprites(ctx);
+            }
+
+            self.render();
+        }
+    }
+
+    //Both inputs are right here, so every frame is confirmed as soon as it is played
+    fn local_versus_update(&mut self, ctx: &mut Context<FightingApp>) {
+        let fps_delta = 1. / FPS;
+        let delta = Instant::now().duration_since(self.last_update);
+        self.accumulator = self.accumulator.saturating_add(delta);
+        self.last_update = Instant::now();
+
+        while self.accumulator.as_secs_f64() > fps_delta {
+            self.accumulator = self.accumulator.saturating_sub(Duration::from_secs_f64(fps_delta));
+            let inputs = match &self.mode {
+                GameMode::LocalVersus(local_versus) => local_versus.game_inputs(),
+                _ => return
+            };
+            self.current_round.advance_match(inputs, &self.game_config.gameplay_config);
+            self.session_frame += 1;
+            self.replay.record(inputs);
+            self.update_sprites(ctx);
+        }
+
+        self.render();
+    }
+
+    //Same frame pacing as online, but GGRS rolls back and re-simulates every frame on its own
+    fn sync_test_update(&mut self, ctx: &mut Context<FightingApp>) {
+        let fps_delta = 1. / FPS;
+        let delta = Instant::now().duration_since(self.last_update);
+        self.accumulator = self.accumulator.saturating_add(delta);
+        self.last_update = Instant::now();
+
+        while self.accumulator.as_secs_f64() > fps_delta {
+            self.accumulator = self.accumulator.saturating_sub(Duration::from_secs_f64(fps_delta));
+            let local_input = self.local_input(0);
+            if let Err(e) = self.sync_test().add_local_input(local_input) {
+                self.report_sync_test_error(e);
+                return;
+            }
+
+            match self.sync_test().session.advance_frame() {
+                Ok(requests) => self.handle_requests(requests),
+                Err(GGRSError::MismatchedChecksum { frame }) => self.report_sync_test_mismatch(frame),
+                Err(e) => {
+                    self.report_sync_test_error(e);
+                    return;
                 }
+            }
 
-                
-                if self.effects_sprites.len() != self.current_round.effects.len() {
-                    let diff = self.current_round.effects.len().abs_diff(self.effects_sprites.len());
-
-                    if self.current_round.effects.len() > self.effects_sprites.len() {
-                        //we need to add the number of new sprites
-                        for _ in 0..diff {
-                            let (fireball_sprite, mut fireball_render_pass) = setup_light_hit_effect(ctx);
-                            fireball_render_pass.atlas = self.light_hit_effect_texture.clone();
-                            self.effects_sprites.push((fireball_sprite, fireball_render_pass))
-                        }
-                    }
-                    else {
-                        //remove the 
-                        self.effects_sprites.truncate(self.effects_sprites.len() - diff);
+            self.update_sprites(ctx);
+        }
+
+        self.render();
+    }
+
+    fn report_sync_test_mismatch(&mut self, frame: Frame) {
+        let check_distance = self.sync_test().check_distance;
+        println!("Sync test mismatch on frame {}, re-simulating it from up to {} frames back gave a different state hash", frame, check_distance);
+        if let Err(e) = self.sync_test().record_mismatch(frame) {
+            self.report_sync_test_error(e);
+            return;
+        }
+        println!("{} mismatches so far, the sync test has been restarted from the current round", self.sync_test().mismatches.len());
+    }
+
+    //Anything other than a mismatch means the session itself broke, so the sync test ends there
+    fn report_sync_test_error(&mut self, error: GGRSError) {
+        println!("Sync test stopped on frame {}: {:?}", self.session_frame, error);
+        println!("{} mismatches were found before it stopped", self.sync_test().mismatches.len());
+        self.sync_test().error = Some(format!("the sync test stopped: {:?}", error));
+    }
+
+    //Plays the replay back at the normal frame rate, no session so nothing can be rolled back
+    fn replay_update(&mut self, ctx: &mut Context<FightingApp>) {
+        let fps_delta = 1. / FPS;
+        let delta = Instant::now().duration_since(self.last_update);
+        self.accumulator = self.accumulator.saturating_add(delta);
+        self.last_update = Instant::now();
+
+        if let Some(seek) = self.playback_controls.take_seek() {
+            if let GameMode::Replay(replay_player) = &mut self.mode {
+                let gameplay_config = &self.game_config.gameplay_config;
+                match seek {
+                    ReplaySeek::Frames(frames) => {
+                        replay_player.seek_by(frames, &mut self.current_round, gameplay_config);
+                    },
+                    ReplaySeek::NextMarker(kind) => {
+                        replay_player.seek_to_next_marker(kind, &mut self.current_round, gameplay_config);
+                    },
+                    ReplaySeek::PreviousMarker(kind) => {
+                        replay_player.seek_to_previous_marker(kind, &mut self.current_round, gameplay_config);
                     }
                 }
+            }
+            self.update_sprites(ctx);
+        }
 
-                for (index, effect) in self.current_round.effects.iter().enumerate() {
-                    let left = effect.current_frame as u32 * EFFECT_FRAME_WIDTH;
-
-                    let test;
-                    match effect.screen_side {
-                        ScreenSide::Left => {
-                            test = self.effects_sprites[index].1.atlas.subsection(left, left + EFFECT_FRAME_WIDTH, 0, 480).mirror_y();
-                            self.effects_sprites[index].0[0].pos.x = effect.position_x * 0.5f32 as f32 ;//+ (FRAME_WIDTH as f32 / 2.0) * X_SCALE as f32;
-                        },
-                        ScreenSide::Right => {
-                            test = self.effects_sprites[index].1.atlas.subsection(left, left + EFFECT_FRAME_WIDTH, 0, 480);
-                            self.effects_sprites[index].0[0].pos.x = effect.position_x  - (FRAME_WIDTH as f32 * 0.9);// - (FRAME_WIDTH as f32 / 2.0);
-                            self.effects_sprites[index].0[0].pos.y = -480.0 * 0.2f32;
-                        }
-                    }
-                    self.effects_sprites[index].0[0].texture = test;
+        while self.accumulator.as_secs_f64() > fps_delta {
+            self.accumulator = self.accumulator.saturating_sub(Duration::from_secs_f64(fps_delta));
+            let frames_to_play = self.playback_controls.frames_to_play();
+            if let GameMode::Replay(replay_player) = &mut self.mode {
+                for _ in 0..frames_to_play {
+                    replay_player.step(&mut self.current_round, &self.game_config.gameplay_config);
                 }
+            }
+            self.update_sprites(ctx);
+        }
+
+        self.render();
+        self.render_replay_timeline();
+    }
 
+    //The bar along the bottom showing how far through the replay we are, with a tick for every round start and hit found so far
+    fn render_replay_timeline(&mut self) {
+        let replay_player = match &self.mode {
+            GameMode::Replay(replay_player) => replay_player,
+            _ => return
+        };
+        let frame_count = replay_player.frame_count().max(1);
+        let frame_to_x = |frame: usize| TIMELINE_X + TIMELINE_WIDTH * (frame as f32 / frame_count as f32);
+
+        let timeline_sprites = &mut self.ui.timeline.0;
+        timeline_sprites.truncate(2);
+        timeline_sprites[1].size.x = (frame_to_x(replay_player.frame) - TIMELINE_X) as u16;
+        for marker in replay_player.markers.iter() {
+            let mut tick = timeline_sprites[0];
+            tick.pos = Vector3::new(frame_to_x(marker.frame), TIMELINE_Y - 4.0, 0.02);
+            if marker.kind == ReplayMarkerKind::RoundStart {
+                tick.size = Vector2::new(3, TIMELINE_HEIGHT + 8);
+                tick.color = RGBA8::WHITE;
             }
+            else {
+                tick.size = Vector2::new(2, TIMELINE_HEIGHT);
+                tick.pos.y = TIMELINE_Y;
+                tick.color = RGBA8::RED;
+            }
+            timeline_sprites.push(tick);
+        }
+        self.ui.timeline.1.buffer.set_data(timeline_sprites);
+        self.ui.timeline.1.draw(&self.sprite_shader);
+
+        let mut status = format!("{} / {}", replay_player.frame, replay_player.frame_count());
+        if replay_player.is_finished() {
+            status.push_str("  FINISHED");
+        }
+        else if self.playback_controls.paused {
+            status.push_str("  PAUSED");
+        }
+
+        self.ui.timeline_text.0.clear_text();
+        let layout_settings = LayoutSettings {
+            x: TIMELINE_X,
+            y: TIMELINE_Y + 50.0,
+            max_width: Some(TIMELINE_WIDTH),
+            ..Default::default()
+        };
+        self.ui.timeline_text.0.append(
+            &self.fonts,
+            &layout_settings,
+            &[Text {
+                text: &status,
+                font_index: 0,
+                px: 30.0,
+                color: RGBA8::WHITE,
+                depth: 0.0,
+            }],
+        );
+        self.ui.timeline_text.0.draw(&self.ui.timeline_text.1);
+    }
 
-            //MAGIC NUMBER: 145
-            let length_of_distance = f32::abs(self.character_2_sprites[0].pos.x - self.character_1_sprites[0].pos.x);
-            if length_of_distance > 145.0 {
-                self.camera_transform.set().scale = 145.0 / length_of_distance * 5.0;
+
+    //Moves every sprite to where the current_round says it is
+    fn update_sprites(&mut self, ctx: &mut Context<FightingApp>) {
+        //Update all of the sprites positions
+        //TODO: maybe use a is_dirty flag to update this only when we need to
+        self.sprite_pass_1.atlas = self.game_config.animation_library.get_atlas_for_animation(self.current_round.characters[0].animation_state);
+
+        let frame = self.current_round.characters[0].get_current_animation();
+        if self.current_round.characters[0].screen_side == ScreenSide::Right {
+            self.character_1_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[0].animation_state, frame.current_frame);
+        }
+        else {
+            self.character_1_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[0].animation_state, frame.current_frame).mirror_y();
+        }
+
+        self.character_1_sprites[0].pos.x = self.current_round.characters[0].character_position.x.to_f32();
+        self.character_1_sprites[0].pos.y = self.current_round.characters[0].character_position.y.to_f32();
+
+        self.sprite_pass_2.atlas = self.game_config.animation_library.get_atlas_for_animation(self.current_round.characters[1].animation_state);
+        let frame = self.current_round.characters[1].get_current_animation();
+        if self.current_round.characters[0].screen_side == ScreenSide::Left {
+            self.character_2_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[1].animation_state, frame.current_frame);
+        }
+        else {
+            self.character_2_sprites[0].texture = self.game_config.animation_library.get_atlas_subsection(self.current_round.characters[1].animation_state, frame.current_frame).mirror_y();
+        }
+
+        self.character_2_sprites[0].pos.x = self.current_round.characters[1].character_position.x.to_f32();
+        self.character_2_sprites[0].pos.y = self.current_round.characters[1].character_position.y.to_f32();
+
+        if self.current_round.projectiles.len() != self.projectile_sprites.len() {
+            let diff = self.current_round.projectiles.len().abs_diff(self.projectile_sprites.len());
+            if self.current_round.projectiles.len() > self.projectile_sprites.len() {
+                //we need to add the number of new sprites
+                for _ in 0..diff {
+                    let (fireball_sprite, mut fireball_render_pass) = setup_fireball(ctx);
+                    fireball_render_pass.atlas = self.fireball_texture.clone();
+                    self.projectile_sprites.push((fireball_sprite, fireball_render_pass))
+                }
             }
             else {
-                self.camera_transform.set().scale = 5.0;
+                //remove the 
+                self.projectile_sprites.truncate(self.projectile_sprites.len() - diff);
             }
+        }
 
-            self.camera_transform.set().translation.y =  -108.0 + ((5.0 - self.camera_transform.set().scale) * -43.0);
 
-            self.camera_transform.set().translation.x = -(((self.character_2_sprites[0].pos.x + self.character_1_sprites[0].pos.x) + FRAME_WIDTH as f32) / 2.0);
+        for (index, projectile) in self.current_round.projectiles.iter().enumerate() {
+            let left = projectile.timer.current_frame * FRAME_WIDTH;
 
-            //Rendering
-            let text_color;
-            let current_frame_count = 60 - (self.current_round.round_timer.current_frame / 60);
-            if  current_frame_count > 20 {
-                text_color = RGBA8::new(85, 196, 59, 255);
-            } else if current_frame_count > 10 {
-                text_color = RGBA8::YELLOW;
+            let test;
+            match projectile.screen_side {
+                ScreenSide::Left => {
+                    test = self.projectile_sprites[index].1.atlas.subsection(left, 0 + FRAME_WIDTH, 0, FRAME_HEIGHT).mirror_y();
+                    self.projectile_sprites[index].0[0].pos.x = projectile.position.x.to_f32() * X_SCALE as f32 ;//+ (FRAME_WIDTH as f32 / 2.0) * X_SCALE as f32;
+                },
+                ScreenSide::Right => {
+                    test = self.projectile_sprites[index].1.atlas.subsection(left, 0 + FRAME_WIDTH, 0, FRAME_HEIGHT);
+                    self.projectile_sprites[index].0[0].pos.x = projectile.position.x.to_f32() * X_SCALE as f32 - (FRAME_WIDTH as f32 / 2.0) * X_SCALE as f32;
+                }
+            }
+            self.projectile_sprites[index].0[0].texture = test;
+        }
+
+
+        if self.effects_sprites.len() != self.current_round.effects.len() {
+            let diff = self.current_round.effects.len().abs_diff(self.effects_sprites.len());
+
+            if self.current_round.effects.len() > self.effects_sprites.len() {
+                //we need to add the number of new sprites
+                for _ in 0..diff {
+                    let (fireball_sprite, mut fireball_render_pass) = setup_light_hit_effect(ctx);
+                    fireball_render_pass.atlas = self.light_hit_effect_texture.clone();
+                    self.effects_sprites.push((fireball_sprite, fireball_render_pass))
+                }
             }
             else {
-                text_color = RGBA8::RED;
+                //remove the 
+                self.effects_sprites.truncate(self.effects_sprites.len() - diff);
             }
+        }
+
+        for (index, effect) in self.current_round.effects.iter().enumerate() {
+            let left = effect.current_frame as u32 * EFFECT_FRAME_WIDTH;
+
+            let test;
+            match effect.screen_side {
+                ScreenSide::Left => {
+                    test = self.effects_sprites[index].1.atlas.subsection(left, left + EFFECT_FRAME_WIDTH, 0, 480).mirror_y();
+                    self.effects_sprites[index].0[0].pos.x = effect.position_x.to_f32() * 0.5f32 as f32 ;//+ (FRAME_WIDTH as f32 / 2.0) * X_SCALE as f32;
+                },
+                ScreenSide::Right => {
+                    test = self.effects_sprites[index].1.atlas.subsection(left, left + EFFECT_FRAME_WIDTH, 0, 480);
+                    self.effects_sprites[index].0[0].pos.x = effect.position_x.to_f32()  - (FRAME_WIDTH as f32 * 0.9);// - (FRAME_WIDTH as f32 / 2.0);
+                    self.effects_sprites[index].0[0].pos.y = -480.0 * 0.2f32;
+                }
+            }
+            self.effects_sprites[index].0[0].texture = test;
+        }
+    }
+
+    fn render(&mut self) {
+        //MAGIC NUMBER: 145
+        let length_of_distance = f32::abs(self.character_2_sprites[0].pos.x - self.character_1_sprites[0].pos.x);
+        if length_of_distance > 145.0 {
+            self.camera_transform.set().scale = 145.0 / length_of_distance * 5.0;
+        }
+        else {
+            self.camera_transform.set().scale = 5.0;
+        }
+
+        self.camera_transform.set().translation.y =  -108.0 + ((5.0 - self.camera_transform.set().scale) * -43.0);
+
+        self.camera_transform.set().translation.x = -(((self.character_2_sprites[0].pos.x + self.character_1_sprites[0].pos.x) + FRAME_WIDTH as f32) / 2.0);
+
+        //Rendering
+        let text_color;
+        let current_frame_count = 60 - (self.current_round.round_timer.current_frame / 60);
+        if  current_frame_count > 20 {
+            text_color = RGBA8::new(85, 196, 59, 255);
+        } else if current_frame_count > 10 {
+            text_color = RGBA8::YELLOW;
+        }
+        else {
+            text_color = RGBA8::RED;
+        }
+
+        self.ui.timer_text.0.clear_text();
+
+        let layout_settings = LayoutSettings {
+            x:  WIDTH as f32 / 2.0 - 10.0,
+            y: HEIGHT as f32 - 90.0,
+            max_width: Some(50.0),
+            ..Default::default()
+        };
+
+        self.ui.timer_text.0.append(
+            &self.fonts,
+            &layout_settings,
+            &[Text {
+                text: &(60 - (self.current_round.round_timer.current_frame / 60)).to_string(),
+                font_index: 0,
+                px: 50.0,
+                color: text_color,
+                depth: 0.0,
+            }],
+        );
+
+        self.background_sprite_pass.set_transform(self.camera_transform.matrix());
+        self.background_sprite_pass.buffer.set_data(&self.background_sprite);
+        self.background_sprite_pass.draw(&self.sprite_shader);            
+
+        self.ui.timer_text.0.draw(&self.ui.timer_text.1);
+
+        /*
+        self.ui.backplate.1.buffer.set_data(&mut self.ui.backplate.0);
+        self.ui.backplate.1.draw(&self.sprite_shader);
+        */
+        for projectile_sprites in self.projectile_sprites.iter_mut() {
+            projectile_sprites.1.set_transform(self.camera_transform.matrix());
+            projectile_sprites.1.buffer.set_data(&projectile_sprites.0);
+            projectile_sprites.1.draw(&self.sprite_shader);
+        }
+
+
+        for effect_sprite in self.effects_sprites.iter_mut() {
+            effect_sprite.1.set_transform(self.camera_transform.matrix());
+            effect_sprite.1.buffer.set_data(&effect_sprite.0);
+            effect_sprite.1.draw(&self.sprite_shader);
+        }
+
+
+        self.sprite_pass_2.set_transform(self.camera_transform.matrix());
+        self.sprite_pass_1.set_transform(self.camera_transform.matrix());
+
+        self.sprite_pass_1.buffer.set_data(&self.character_1_sprites);
+        self.sprite_pass_1.draw(&self.pallete_sprite_shader);
 
-            self.ui.timer_text.0.clear_text();
+        self.sprite_pass_2.buffer.set_data(&self.character_2_sprites);
+        self.sprite_pass_2.draw(&self.pallete_sprite_shader);
 
+
+
+        //Render Health Bars
+        let health_ratio_player_one = self.current_round.characters[0].health as f32 / 250.0;
+        let health_ratio_player_two = self.current_round.characters[1].health as f32 / 250.0;
+        if health_ratio_player_one > 0.95 {
+            self.ui.healthbars.0[0].color = RGBA8::GREEN;
+        }
+        if health_ratio_player_one < 0.95 && health_ratio_player_one > 0.25  {
+            self.ui.healthbars.0[0].color = RGBA8::YELLOW;
+        }
+        else if health_ratio_player_one < 0.25 {
+            self.ui.healthbars.0[0].color = RGBA8::RED;
+        }
+
+        if health_ratio_player_two > 0.95 {
+            self.ui.healthbars.0[1].color = RGBA8::GREEN;                
+        }
+        else if health_ratio_player_two < 0.95 && health_ratio_player_two > 0.25 {
+            self.ui.healthbars.0[1].color = RGBA8::YELLOW;
+        }
+        else if health_ratio_player_two < 0.25 {
+            self.ui.healthbars.0[1].color = RGBA8::RED;
+        }
+
+        self.ui.healthbars.0[0].size.x = (480.0 * health_ratio_player_one) as u16;
+        self.ui.healthbars.0[1].size.x = (480.0 * health_ratio_player_two) as u16;
+
+        let removed_amount_player_2 = 1.0 - health_ratio_player_two;
+        self.ui.healthbars.0[1].pos.x = 160.0 + (480.0 * removed_amount_player_2);
+
+        self.ui.healthbars.1.buffer.set_data(&self.ui.healthbars.0);
+        self.ui.healthbars.1.draw(&self.sprite_shader);
+
+        //Render the combo counters, each one sits under the health bar of the character landing the combo
+        self.ui.combo_text.0.clear_text();
+        for (index, combo_counter) in self.current_round.combo_counters.iter().enumerate() {
+            if combo_counter.should_display() == false {
+                continue;
+            }
+
+            let x;
+            if index == 0 {
+                x = WIDTH as f32 / 2.0 + 95.0;
+            }
+            else {
+                x = 160.0;
+            }
             let layout_settings = LayoutSettings {
-                x:  WIDTH as f32 / 2.0 - 10.0,
-                y: HEIGHT as f32 - 90.0,
-                max_width: Some(50.0),
+                x,
+                y: HEIGHT as f32 - 180.0,
+                max_width: Some(480.0),
                 ..Default::default()
             };
 
-            self.ui.timer_text.0.append(
+            self.ui.combo_text.0.append(
                 &self.fonts,
                 &layout_settings,
                 &[Text {
-                    text: &(60 - (self.current_round.round_timer.current_frame / 60)).to_string(),
+                    text: &format!("{} HITS {} DMG", combo_counter.hits, combo_counter.damage),
                     font_index: 0,
-                    px: 50.0,
-                    color: text_color,
+                    px: 40.0,
+                    color: RGBA8::WHITE,
                     depth: 0.0,
                 }],
             );
+        }
+        self.ui.combo_text.0.draw(&self.ui.combo_text.1);
 
-            self.background_sprite_pass.set_transform(self.camera_transform.matrix());
-            self.background_sprite_pass.buffer.set_data(&self.background_sprite);
-            self.background_sprite_pass.draw(&self.sprite_shader);            
-
-            self.ui.timer_text.0.draw(&self.ui.timer_text.1);
+        self.render_player_names();
+    }
 
-            /*
-            self.ui.backplate.1.buffer.set_data(&mut self.ui.backplate.0);
-            self.ui.backplate.1.draw(&self.sprite_shader);
-            */
-            for projectile_sprites in self.projectile_sprites.iter_mut() {
-                projectile_sprites.1.set_transform(self.camera_transform.matrix());
-                projectile_sprites.1.buffer.set_data(&projectile_sprites.0);
-                projectile_sprites.1.draw(&self.sprite_shader);
+    //Each name sits above its health bar, with the local player marked, and at the start of every round
+    //a banner says which side of the screen the local player is starting on
+    fn render_player_names(&mut self) {
+        let you = self.you();
+        self.ui.name_text.0.clear_text();
+        for index in 0..2 {
+            let x;
+            if index == 0 {
+                x = WIDTH as f32 / 2.0 + 95.0;
             }
-
-
-            for effect_sprite in self.effects_sprites.iter_mut() {
-                effect_sprite.1.set_transform(self.camera_transform.matrix());
-                effect_sprite.1.buffer.set_data(&effect_sprite.0);
-                effect_sprite.1.draw(&self.sprite_shader);
+            else {
+                x = 160.0;
             }
+            let layout_settings = LayoutSettings {
+                x,
+                y: HEIGHT as f32 - 82.0,
+                max_width: Some(480.0),
+                ..Default::default()
+            };
 
-
-            self.sprite_pass_2.set_transform(self.camera_transform.matrix());
-            self.sprite_pass_1.set_transform(self.camera_transform.matrix());
-
-            self.sprite_pass_1.buffer.set_data(&self.character_1_sprites);
-            self.sprite_pass_1.draw(&self.pallete_sprite_shader);
-            
-            self.sprite_pass_2.buffer.set_data(&self.character_2_sprites);
-            self.sprite_pass_2.draw(&self.pallete_sprite_shader);
-            
-
-            
-            //Render Health Bars
-            let health_ratio_player_one = self.current_round.characters[0].health as f32 / 250.0;
-            let health_ratio_player_two = self.current_round.characters[1].health as f32 / 250.0;
-            if health_ratio_player_one > 0.95 {
-                self.ui.healthbars.0[0].color = RGBA8::GREEN;
+            let name;
+            let color;
+            if you == Some(index) {
+                name = format!("P{} {} - YOU", index + 1, self.player_name(index));
+                color = RGBA8::YELLOW;
             }
-            if health_ratio_player_one < 0.95 && health_ratio_player_one > 0.25  {
-                self.ui.healthbars.0[0].color = RGBA8::YELLOW;
-            }
-            else if health_ratio_player_one < 0.25 {
-                self.ui.healthbars.0[0].color = RGBA8::RED;
+            else {
+                name = format!("P{} {}", index + 1, self.player_name(index));
+                color = RGBA8::WHITE;
             }
+            self.ui.name_text.0.append(
+                &self.fonts,
+                &layout_settings,
+                &[Text {
+                    text: &name,
+                    font_index: 0,
+                    px: 30.0,
+                    color,
+                    depth: 0.0,
+                }],
+            );
+        }
 
-            if health_ratio_player_two > 0.95 {
-                self.ui.healthbars.0[1].color = RGBA8::GREEN;                
-            }
-            else if health_ratio_player_two < 0.95 && health_ratio_player_two > 0.25 {
-                self.ui.healthbars.0[1].color = RGBA8::YELLOW;
-            }
-            else if health_ratio_player_two < 0.25 {
-                self.ui.healthbars.0[1].color = RGBA8::RED;
+        if let Some(you) = you {
+            if self.current_round.round_timer.current_frame < ROUND_INTRO_FRAMES {
+                let side = match self.current_round.characters[you].screen_side {
+                    ScreenSide::Left => "LEFT",
+                    ScreenSide::Right => "RIGHT"
+                };
+                let layout_settings = LayoutSettings {
+                    x: WIDTH as f32 / 2.0 - 275.0,
+                    y: HEIGHT as f32 / 2.0 + 100.0,
+                    max_width: Some(600.0),
+                    ..Default::default()
+                };
+                self.ui.name_text.0.append(
+                    &self.fonts,
+                    &layout_settings,
+                    &[Text {
+                        text: &format!("YOU ARE P{} ON THE {}", you + 1, side),
+                        font_index: 0,
+                        px: 50.0,
+                        color: RGBA8::YELLOW,
+                        depth: 0.0,
+                    }],
+                );
             }
-
-            self.ui.healthbars.0[0].size.x = (480.0 * health_ratio_player_one) as u16;
-            self.ui.healthbars.0[1].size.x = (480.0 * health_ratio_player_two) as u16;
-
-            let removed_amount_player_2 = 1.0 - health_ratio_player_two;
-            self.ui.healthbars.0[1].pos.x = 160.0 + (480.0 * removed_amount_player_2);
-
-            self.ui.healthbars.1.buffer.set_data(&self.ui.healthbars.0);
-            self.ui.healthbars.1.draw(&self.sprite_shader);
-
         }
+        self.ui.name_text.0.draw(&self.ui.name_text.1);
     }
 
     // for each request, call the appropriate function
     pub fn handle_requests(&mut self, requests: Vec<GGRSRequest<GGRSConfig>>) {
         for request in requests {
             match request {
-                GGRSRequest::LoadGameState { cell, frame: _ } => self.load_game_state(cell),
+                GGRSRequest::LoadGameState { cell, frame } => self.load_game_state(cell, frame),
                 GGRSRequest::SaveGameState { cell, frame } => self.save_game_state(cell, frame),
                 GGRSRequest::AdvanceFrame { inputs } => self.advance_frame(inputs),
             }
@@ -469,43 +980,7 @@ impl<'a> Game<'a> {
 
     #[allow(dead_code)]
     // creates a compact representation of currently pressed keys and serializes it
-    pub fn local_input(&self, handle: PlayerHandle) -> NetInput {
-        let mut input: u16 = 0;
-
-        // ugly, but it works...
-        // player 1 with WASD
-
-        if self.local_input.left_key_down {
-            input |= INPUT_LEFT;
-        }
-        if self.local_input.right_key_down {
-            input |= INPUT_RIGHT;
-        }
-        if self.local_input.down_key_down {
-            input |= INPUT_DOWN;
-        }
-        if self.local_input.light_attack {
-            input |= INPUT_LIGHT_ATTACK;
-        }
-        if self.local_input.medium_attack {
-            input |= INPUT_MEDIUM_ATTACK;
-        }
-        if self.local_input.heavy_attack {
-            input |= INPUT_HEAVY_ATTACK;
-        }
-        if self.local_input.light_kick {
-            input |= INPUT_LIGHT_KICK;
-        }
-        if self.local_input.medium_kick {
-            input |= INPUT_MEDIUM_KICK;
-        }
-        if self.local_input.heavy_kick {
-            input |= INPUT_HEAVY_KICK;
-        }
-        if self.local_input.jump_down {
-            input |= INPUT_JUMP;
-        }
-
-        return NetInput{ input};//input.to_le_bytes();.to_vec();
+    pub fn local_input(&self, _handle: PlayerHandle) -> NetInput {
+        return self.local_input.to_game_input();
     }
 }
\ No newline at end of file
@@ -0,0 +1,88 @@
+use storm::event::*;
+use super::*;
+
+//Keyboard handling lives with the client so the simulation never needs to know about storm
+pub trait KeyboardInput {
+    fn key_down(&mut self, keyboard_button: KeyboardButton);
+    fn key_up(&mut self, keyboard_button: KeyboardButton);
+}
+
+//Which keys drive an Input, one player gets the whole keyboard, local versus splits it down the middle
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum KeyboardLayout {
+    Full, //Arrows to move, Q W E punches, A S D kicks
+    LeftSide, //W A S D to move, R T Y punches, F G H kicks
+    RightSide //Arrows to move, U I O punches, J K L kicks
+}
+
+impl KeyboardLayout {
+    //The button on the Input that a key drives in this layout, None if the key is not part of it
+    fn button<'a>(&self, input: &'a mut Input, keyboard_button: KeyboardButton) -> Option<&'a mut bool> {
+        match self {
+            KeyboardLayout::Full => {
+                match keyboard_button {
+                    KeyboardButton::Left => Some(&mut input.left_key_down),
+                    KeyboardButton::Right => Some(&mut input.right_key_down),
+                    KeyboardButton::Down => Some(&mut input.down_key_down),
+                    KeyboardButton::Up => Some(&mut input.jump_down),
+                    KeyboardButton::Q => Some(&mut input.light_attack),
+                    KeyboardButton::W => Some(&mut input.medium_attack),
+                    KeyboardButton::E => Some(&mut input.heavy_attack),
+                    KeyboardButton::A => Some(&mut input.light_kick),
+                    KeyboardButton::S => Some(&mut input.medium_kick),
+                    KeyboardButton::D => Some(&mut input.heavy_kick),
+                    _ => None
+                }
+            },
+            KeyboardLayout::LeftSide => {
+                match keyboard_button {
+                    KeyboardButton::A => Some(&mut input.left_key_down),
+                    KeyboardButton::D => Some(&mut input.right_key_down),
+                    KeyboardButton::S => Some(&mut input.down_key_down),
+                    KeyboardButton::W => Some(&mut input.jump_down),
+                    KeyboardButton::R => Some(&mut input.light_attack),
+                    KeyboardButton::T => Some(&mut input.medium_attack),
+                    KeyboardButton::Y => Some(&mut input.heavy_attack),
+                    KeyboardButton::F => Some(&mut input.light_kick),
+                    KeyboardButton::G => Some(&mut input.medium_kick),
+                    KeyboardButton::H => Some(&mut input.heavy_kick),
+                    _ => None
+                }
+            },
+            KeyboardLayout::RightSide => {
+                match keyboard_button {
+                    KeyboardButton::Left => Some(&mut input.left_key_down),
+                    KeyboardButton::Right => Some(&mut input.right_key_down),
+                    KeyboardButton::Down => Some(&mut input.down_key_down),
+                    KeyboardButton::Up => Some(&mut input.jump_down),
+                    KeyboardButton::U => Some(&mut input.light_attack),
+                    KeyboardButton::I => Some(&mut input.medium_attack),
+                    KeyboardButton::O => Some(&mut input.heavy_attack),
+                    KeyboardButton::J => Some(&mut input.light_kick),
+                    KeyboardButton::K => Some(&mut input.medium_kick),
+                    KeyboardButton::L => Some(&mut input.heavy_kick),
+                    _ => None
+                }
+            }
+        }
+    }
+
+    //Keys that are not part of this layout are ignored
+    pub fn set_key(&self, input: &mut Input, keyboard_button: KeyboardButton, down: bool) {
+        if let Some(button) = self.button(input, keyboard_button) {
+            *button = down;
+        }
+    }
+}
+
+impl KeyboardInput for Input {
+    //TODO: let this be configurable so we can handle
+    //Controllers, key rebinds, etc
+    fn key_down(&mut self, keyboard_button: KeyboardButton) {
+        KeyboardLayout::Full.set_key(self, keyboard_button, true);
+    }
+
+    fn key_up(&mut self, keyboard_button: KeyboardButton) {
+        KeyboardLayout::Full.set_key(self, keyboard_button, false);
+    }
+}
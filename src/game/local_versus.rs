@@ -0,0 +1,40 @@
+use storm::event::*;
+use super::*;
+
+//Two players sharing one keyboard, nothing goes over the network so the Round is advanced directly
+//Player one starts on the right, so they get the arrow keys on the right side of the keyboard
+pub struct LocalVersus {
+    pub inputs: [Input; 2],
+    pub layouts: [KeyboardLayout; 2]
+}
+
+impl LocalVersus {
+    pub fn new() -> LocalVersus {
+        LocalVersus {
+            inputs: [Input::new(), Input::new()],
+            layouts: [KeyboardLayout::RightSide, KeyboardLayout::LeftSide]
+        }
+    }
+
+    pub fn key_down(&mut self, keyboard_button: KeyboardButton) {
+        for player in 0..2 {
+            self.layouts[player].set_key(&mut self.inputs[player], keyboard_button, true);
+        }
+    }
+
+    pub fn key_up(&mut self, keyboard_button: KeyboardButton) {
+        for player in 0..2 {
+            self.layouts[player].set_key(&mut self.inputs[player], keyboard_button, false);
+        }
+    }
+
+    pub fn game_inputs(&self) -> [NetInput; 2] {
+        return [self.inputs[0].to_game_input(), self.inputs[1].to_game_input()];
+    }
+}
+
+impl Default for LocalVersus {
+    fn default() -> LocalVersus {
+        return LocalVersus::new();
+    }
+}
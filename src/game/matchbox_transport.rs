@@ -0,0 +1,99 @@
+use ggrs::PlayerType;
+
+use std::collections::HashMap;
+use futures::Future;
+use matchbox_socket::{RoomEvent, WebRtcSocket};
+use super::*;
+
+//The default matchbox server, the one web.bat's build talks to
+pub const MATCHBOX_URL: &str = "ws://127.0.0.1:3536";
+
+//Finds the match through a matchbox server and plays it over WebRTC, browsers can not open a UDP socket so this is
+//how they play, a native client can use it too to play someone in a browser
+pub struct MatchboxTransport {
+    socket: WebRtcSocket,
+    local_profile: PlayerProfile,
+    peer_profiles: HashMap<String, PlayerProfile> //Who the matchbox server told us each peer is
+}
+
+impl MatchboxTransport {
+    pub fn new(matchbox_url: &str, match_request: MatchRequest, profile: PlayerProfile) -> MatchboxTransport {
+        //PlayerProfile only lets through characters that are safe in a URL, apart from the space
+        let name = profile.name.replace(' ', "%20");
+        let room_url = match match_request {
+            MatchRequest::FindMatch => format!("{}/next_2?name={}&pallete={}", matchbox_url, name, profile.pallete),
+            MatchRequest::CreateRoom => format!("{}/private?private=create&name={}&pallete={}", matchbox_url, name, profile.pallete),
+            MatchRequest::JoinRoom { code } => format!("{}/{}?private=join&name={}&pallete={}", matchbox_url, normalize_room_code(&code), name, profile.pallete)
+        };
+        let (socket, message_loop) = WebRtcSocket::new(room_url);
+        spawn_message_loop(message_loop);
+
+        MatchboxTransport {
+            socket,
+            local_profile: profile,
+            peer_profiles: HashMap::new()
+        }
+    }
+}
+
+//Nothing is sent or received until the socket's message loop runs
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_message_loop(message_loop: impl Future<Output = ()> + Send + 'static) {
+    std::thread::spawn(move || futures::executor::block_on(message_loop));
+}
+
+//There are no threads in the browser, so the message loop runs alongside the page
+#[cfg(target_arch = "wasm32")]
+fn spawn_message_loop(message_loop: impl Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(message_loop);
+}
+
+impl Transport for MatchboxTransport {
+    fn poll_players(&mut self) -> Result<Option<Vec<MatchPlayer>>, String> {
+        for room_event in self.socket.room_events() {
+            match room_event {
+                RoomEvent::RoomCreated(code) => {
+                    println!("Created room {}, give this code to the other player", code);
+                },
+                RoomEvent::RoomError(reason) => {
+                    return Err(format!("the matchbox server turned us away: {}", reason));
+                },
+                //The server sends these ahead of the peer connecting, so by the time it has we know its name
+                RoomEvent::PeerProfile { peer, name, pallete } => {
+                    self.peer_profiles.insert(peer, PlayerProfile::new(&name, pallete));
+                }
+            }
+        }
+
+        self.socket.accept_new_connections();
+        if self.socket.connected_peers().len() + 1 < 2 {
+            return Ok(None);
+        }
+
+        //Every peer has to come up with the same handles, so they go in the order of the peer ids
+        let mut ids = self.socket.connected_peers();
+        ids.push(self.socket.id().clone());
+        ids.sort();
+        let mut players = vec![];
+        for id in ids {
+            if id == *self.socket.id() {
+                players.push(MatchPlayer { player_type: PlayerType::Local, profile: self.local_profile.clone() });
+            }
+            else {
+                let profile = self.peer_profiles.get(&id).cloned().unwrap_or_else(|| PlayerProfile::new("", 0));
+                players.push(MatchPlayer { player_type: PlayerType::Remote(PeerAddress::WebRtc(id)), profile });
+            }
+        }
+        return Ok(Some(players));
+    }
+
+    fn send_to(&mut self, packet: &[u8], address: &PeerAddress) {
+        if let PeerAddress::WebRtc(id) = address {
+            self.socket.send(packet.to_vec().into_boxed_slice(), id.clone());
+        }
+    }
+
+    fn receive_all(&mut self) -> Result<Vec<(PeerAddress, Vec<u8>)>, String> {
+        return Ok(self.socket.receive().into_iter().map(|(id, packet)| (PeerAddress::WebRtc(id), packet.into_vec())).collect());
+    }
+}
@@ -4,6 +4,7 @@ use storm::{graphics::shaders::text::{TextShaderPass, TextShader}, fontdue::Font
 use storm::graphics::shaders::text::{Text};
 use storm::fontdue::layout::LayoutSettings;
 use storm::asset::Asset;
+use storm::event::KeyboardButton;
 use crate::*;
 use super::*;
 
@@ -35,6 +36,13 @@ impl Button {
     }
 }
 
+//What kind of match the menu starts, picked with Up and Down before clicking through
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MatchType {
+    Online,
+    LocalVersus
+}
+
 pub struct Menu {
     text_shader_pass: TextShaderPass,
     text_shader: TextShader,
@@ -42,7 +50,9 @@ pub struct Menu {
     button: Button,
     button_sprites: [Sprite;1],
     button_shader_pass: SpriteShaderPass,
-    sprite_shader: SpriteShader
+    sprite_shader: SpriteShader,
+    pub selected_match_type: MatchType,
+    pub error: Option<String> //Why the last match could not be started
 }
 
 impl Menu {
@@ -66,7 +76,9 @@ impl Menu {
             },
             button_sprites,
             button_shader_pass,
-            sprite_shader: SpriteShader::new(ctx)
+            sprite_shader: SpriteShader::new(ctx),
+            selected_match_type: MatchType::Online,
+            error: None
         }
     }
     
@@ -104,6 +116,7 @@ impl Menu {
 
     pub fn tick(&mut self, ctx: &mut Context<FightingApp>) -> GameState {
         if self.button.confirmed_click  {
+            self.error = None;
             return GameState::Game;
         }
         ctx.clear(ClearMode::new().with_color(RGBA8::BLUE).with_depth(0.0, DepthTest::Greater));
@@ -119,26 +132,91 @@ impl Menu {
         self.button_shader_pass.draw(&self.sprite_shader);
 
         self.text_shader_pass.clear_text();
-        let layout_settings = LayoutSettings {
-            x: 0.0,
-            y: 420.0,
-            max_width: Some(2000.0),
-            ..Default::default()
-        };
-        self.text_shader_pass.append(
-            &self.fonts,
-            &layout_settings,
-            &[Text {
-                text: "",
-                font_index: 0,
-                px: 200.0,
-                color: RGBA8::WHITE,
-                depth: 0.0,
-        }]);
+        let match_types = [(MatchType::Online, "ONLINE"), (MatchType::LocalVersus, "LOCAL VERSUS")];
+        for (index, (match_type, name)) in match_types.iter().enumerate() {
+            let color;
+            if *match_type == self.selected_match_type {
+                color = RGBA8::YELLOW;
+            }
+            else {
+                color = RGBA8::WHITE;
+            }
+            let layout_settings = LayoutSettings {
+                x: 40.0,
+                y: 160.0 - index as f32 * 60.0,
+                max_width: Some(2000.0),
+                ..Default::default()
+            };
+            self.text_shader_pass.append(
+                &self.fonts,
+                &layout_settings,
+                &[Text {
+                    text: name,
+                    font_index: 0,
+                    px: 50.0,
+                    color,
+                    depth: 0.0,
+            }]);
+        }
+        if self.selected_match_type == MatchType::LocalVersus {
+            let layout_settings = LayoutSettings {
+                x: 40.0,
+                y: 40.0,
+                max_width: Some(2000.0),
+                ..Default::default()
+            };
+            self.text_shader_pass.append(
+                &self.fonts,
+                &layout_settings,
+                &[Text {
+                    text: "P1: ARROWS, U I O PUNCH, J K L KICK    P2: W A S D, R T Y PUNCH, F G H KICK",
+                    font_index: 0,
+                    px: 30.0,
+                    color: RGBA8::WHITE,
+                    depth: 0.0,
+            }]);
+        }
+        if let Some(error) = &self.error {
+            let layout_settings = LayoutSettings {
+                x: 40.0,
+                y: -40.0,
+                max_width: Some(1360.0),
+                ..Default::default()
+            };
+            self.text_shader_pass.append(
+                &self.fonts,
+                &layout_settings,
+                &[Text {
+                    text: error,
+                    font_index: 0,
+                    px: 30.0,
+                    color: RGBA8::RED,
+                    depth: 0.0,
+            }]);
+        }
         self.text_shader_pass.draw(&self.text_shader);
         return GameState::Menu;
     }
 
+    //Coming back from a match that never started, the button has to be clicked again to retry
+    pub fn show_error(&mut self, reason: String) {
+        self.error = Some(reason);
+        self.button.started_click = false;
+        self.button.confirmed_click = false;
+    }
+
+    pub fn key_down(&mut self, keyboard_button: KeyboardButton) {
+        match keyboard_button {
+            KeyboardButton::Up => {
+                self.selected_match_type = MatchType::Online;
+            },
+            KeyboardButton::Down => {
+                self.selected_match_type = MatchType::LocalVersus;
+            },
+            _ => {}
+        }
+    }
+
     pub fn mouse_down(&mut self, position: Vector2<f32>) {
         self.button.was_clicked_on(position);
     }
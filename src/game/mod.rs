@@ -1,39 +1,41 @@
 mod animation;
-mod character;
-mod round;
 mod game;
-mod input;
-mod collision;
-mod projectile;
 mod menu;
 mod setup_functions;
-mod character_sheet;
-mod effects;
+mod keyboard;
+mod desync;
+mod playback;
+mod sync_test;
+mod local_versus;
 //mod state_machine;
+mod net;
+mod transport;
+mod matchbox_transport;
 
-#[cfg(target_arch = "wasm32")]
-mod web_net;
+#[cfg(not(target_arch = "wasm32"))]
+mod udp_transport;
 
 #[cfg(not(target_arch = "wasm32"))]
-mod net;
+mod cupid_transport;
 
 
+pub use storm_fighting::*;
 pub use animation::*;
-pub use character::*;
-pub use round::*;
 pub use game::*;
-pub use collision::*;
-pub use input::*;
-pub use projectile::*;
 pub use menu::*;
 pub use setup_functions::*;
-pub use character_sheet::*;
-pub use effects::*;
+pub use keyboard::*;
+pub use desync::*;
+pub use playback::*;
+pub use sync_test::*;
+pub use local_versus::*;
 //pub use state_machine::*;
-
-#[cfg(not(target_arch = "wasm32"))]
 pub use net::*;
+pub use transport::*;
+pub use matchbox_transport::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use udp_transport::*;
 
-#[cfg(target_arch = "wasm32")]
-pub use web_net::*;
\ No newline at end of file
+#[cfg(not(target_arch = "wasm32"))]
+pub use cupid_transport::*;
@@ -1,116 +1,165 @@
 use ggrs::SessionState;
-use ggrs::{P2PSession, PlayerType, SessionBuilder, UdpNonBlockingSocket};
+use ggrs::{DesyncDetection, Frame, GGRSError, GGRSEvent, P2PSession, PlayerType, SessionBuilder};
 
-use std::io::prelude::*;
 use std::marker::PhantomData;
-use std::net::TcpStream;
-use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
 use crate::*;
 
 pub const FPS: f64 = 60.0;
-pub const CUPID_PORT: &str = "7878";
-
 
 pub enum NetState {
-    Connecting,
-    Live
+    Connecting, //The transport is still finding or connecting to the other player
+    Live,
+    Failed(String) //The match never started or the connection broke, the reason is shown back on the menu
 }
 
-
+//An online match, the same on native and in the browser, what differs is the Transport underneath
 pub struct Net<'a> {
     pub session: Option<P2PSession<GGRSConfig>>,
     pub local_handle: usize,
     marker: PhantomData<&'a ()>,
-    pub state: NetState
+    pub state: NetState,
+    transport: Arc<Mutex<Box<dyn Transport>>>, //Shared with the TransportSocket GGRS owns, used to send checksum reports
+    received_checksums: Arc<Mutex<Vec<ChecksumReport>>>,
+    transport_error: Arc<Mutex<Option<String>>>, //Set by the TransportSocket if receiving ever fails
+    remote_addrs: Vec<PeerAddress>,
+    pub player_profiles: [PlayerProfile; 2] //Indexed by GGRS player handle, filled in once everyone has connected
 }
 
 impl<'a> Net<'a> {
-    pub fn new(session: P2PSession<GGRSConfig>, local_handle: usize) -> Net<'a>  {
+    pub fn new(transport: Box<dyn Transport>, local_profile: PlayerProfile) -> Net<'a>  {
         Net {
-            session: Some(session),
-            local_handle,
+            session: None,
+            local_handle: 0,
             marker: PhantomData,
-            state: NetState::Live
+            state: NetState::Connecting,
+            transport: Arc::new(Mutex::new(transport)),
+            received_checksums: Arc::new(Mutex::new(vec![])),
+            transport_error: Arc::new(Mutex::new(None)),
+            remote_addrs: vec![],
+            player_profiles: [local_profile.clone(), local_profile]
         }
     }
 
+    //A direct match can fail right away, on a bad address or a port that is already taken
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn launch_session(matchmaker: Matchmaker, match_request: MatchRequest, profile: PlayerProfile) -> Result<Net<'a>, String> {
+        let transport: Box<dyn Transport> = match &matchmaker {
+            Matchmaker::Cupid(cupid_ip_addr) => Box::new(CupidTransport::new(cupid_ip_addr, match_request, profile.clone())),
+            Matchmaker::Matchbox(matchbox_url) => Box::new(MatchboxTransport::new(matchbox_url, match_request, profile.clone())),
+            Matchmaker::Direct { local_port, players, spectators } => Box::new(UdpTransport::direct(*local_port, players, spectators, profile.clone())?)
+        };
+        return Ok(Net::new(transport, profile));
+    }
+
+    //Browsers can only reach the other player through WebRTC
+    #[cfg(target_arch = "wasm32")]
+    pub fn launch_session(matchmaker: Matchmaker, match_request: MatchRequest, profile: PlayerProfile) -> Result<Net<'a>, String> {
+        let transport: Box<dyn Transport> = match &matchmaker {
+            Matchmaker::Cupid(_) | Matchmaker::Direct { .. } => return Err(String::from("browsers can not open a UDP socket, the match has to go through matchbox")),
+            Matchmaker::Matchbox(matchbox_url) => Box::new(MatchboxTransport::new(matchbox_url, match_request, profile.clone()))
+        };
+        return Ok(Net::new(transport, profile));
+    }
+
     pub fn is_running(&self) -> bool {
         return self.session.as_ref().unwrap().current_state() == SessionState::Running;
     }
 
-    pub fn add_local_input(&mut self, handle: usize, inputs: NetInput) {
-        self.session.as_mut().unwrap().add_local_input(handle, inputs).unwrap();
+    //The newest frame GGRS has everyone's inputs for, nothing up to it will be rolled back
+    pub fn confirmed_frame(&self) -> Frame {
+        return self.session.as_ref().unwrap().confirmed_frame();
     }
 
-    pub fn launch_session(cupid_ip_addr: &str) -> Net<'a> {
-        //Connect to the Cupid server
-        let cupid_socket = cupid_ip_addr.to_string() + ":" + CUPID_PORT;
-        let mut stream = TcpStream::connect(cupid_socket).unwrap();
-
-        let mut players = vec![String::from("localhost")];
-    
-        let mut buffer = [0;512];
-        let mut message = vec![];
-        stream.read(&mut buffer).unwrap();
-        let mut has_seen_one = false;
-        for value in buffer {
-            if value == 96 {
-                if has_seen_one == false {
-                    has_seen_one = true;
-                }
-                else {
-                    let test : String = message.iter().collect();
-                    let parts : Vec<String> = test.split('#').map(|x|x.to_string()).collect();
-    
-                    let player_pos:char = parts[1].chars().nth(0).unwrap();
-                    if player_pos == '\u{1}' {    
-                        players.insert(0, parts[0].clone())
-                    }
-                    else {
-                        players.push(parts[0].clone());
-                    }
-                    break;
+    pub fn add_local_input(&mut self, handle: usize, inputs: NetInput) -> Result<(), GGRSError> {
+        return self.session.as_mut().unwrap().add_local_input(handle, inputs);
+    }
+
+    //The match can not go on, the game goes back to the menu with the reason
+    pub fn fail(&mut self, reason: String) {
+        self.state = NetState::Failed(reason);
+    }
+
+    pub fn tick(&mut self) {
+        match self.state {
+            NetState::Connecting => {
+                self.connecting_tick();
+            },
+            NetState::Live => {
+                self.session.as_mut().unwrap().poll_remote_clients();
+                let transport_error = self.transport_error.lock().unwrap().take();
+                if let Some(reason) = transport_error {
+                    self.fail(reason);
                 }
+            },
+            NetState::Failed(_) => {}
+        }
+    }
+
+    //Waits on the transport until everyone is connected, then starts the GGRS session over it
+    fn connecting_tick(&mut self) {
+        let polled = self.transport.lock().unwrap().poll_players();
+        let players = match polled {
+            Ok(Some(players)) => players,
+            Ok(None) => return,
+            Err(reason) => {
+                self.fail(reason);
+                return;
             }
-            else {
-                message.push(value as char);
-            }
+        };
+
+        if let Err(e) = self.start_session(players) {
+            self.fail(format!("GGRS could not start the session: {:?}", e));
         }
+    }
 
-        //Now that we have the info kill our connection
-        let _ = stream.shutdown(std::net::Shutdown::Both);
-    
-        // read cmd line arguments
-        let mut local_handle = 0;
+    //Everyone the transport found goes into the session in GGRS player handle order
+    fn start_session(&mut self, players: Vec<MatchPlayer>) -> Result<(), GGRSError> {
         let num_players = 2;//This is a peer to peer fighting game, there will only ever be 2 players
-        assert!(num_players > 0);
         let mut sess = SessionBuilder::<GGRSConfig>::new()
             .with_num_players(num_players)
-            .with_fps(FPS as usize).unwrap()
-            .with_input_delay(2);
-
-        // add players
-        for (i, player_addr) in players.iter().enumerate() {
-            // local player
-            if *player_addr == "localhost" {
-                sess = sess.add_player(PlayerType::Local, i).unwrap();
-                local_handle = i;
-            } else {
-                // remote players
-                let remote_addr : SocketAddr = player_addr.parse().unwrap();
-                println!("{:?}", remote_addr);
-                sess = sess.add_player(PlayerType::Remote(remote_addr), i).unwrap();
+            .with_fps(FPS as usize)?
+            .with_input_delay(2)
+            //GGRS compares its own checksums with the peer's too, on the same frames we send ChecksumReports for
+            .with_desync_detection_mode(DesyncDetection::On { interval: CHECKSUM_PERIOD as u32 });
+
+        for (handle, player) in players.into_iter().enumerate() {
+            match &player.player_type {
+                PlayerType::Local => {
+                    self.local_handle = handle;
+                },
+                PlayerType::Remote(address) => {
+                    self.remote_addrs.push(address.clone());
+                },
+                _ => {}
+            }
+            if handle < num_players {
+                self.player_profiles[handle] = player.profile.clone();
             }
+            sess = sess.add_player(player.player_type, handle)?;
         }
-        println!("{:?}", stream.local_addr());
-        let socket = UdpNonBlockingSocket::bind_to_port(stream.local_addr().unwrap().port()).unwrap();
-        let mut sess = sess.start_p2p_session(socket).unwrap();
 
-        return Net::new(sess, local_handle);
+        let socket = TransportSocket::new(self.transport.clone());
+        self.received_checksums = socket.received_checksums.clone();
+        self.transport_error = socket.error.clone();
+        self.session = Some(sess.start_p2p_session(socket)?);
+        self.state = NetState::Live;
+        return Ok(());
     }
 
-    pub fn tick(&mut self) {
-        self.session.as_mut().unwrap().poll_remote_clients();
+    pub fn send_checksum(&mut self, report: ChecksumReport) {
+        let packet = TransportSocket::checksum_packet(&report);
+        let mut transport = self.transport.lock().unwrap();
+        for remote_addr in self.remote_addrs.iter() {
+            transport.send_to(&packet, remote_addr);
+        }
+    }
+
+    pub fn received_checksums(&mut self) -> Vec<ChecksumReport> {
+        return self.received_checksums.lock().unwrap().drain(..).collect();
     }
-}
 
+    pub fn events(&mut self) -> Vec<GGRSEvent<GGRSConfig>> {
+        return self.session.as_mut().unwrap().events().collect();
+    }
+}
@@ -0,0 +1,97 @@
+use storm::event::*;
+use super::*;
+
+//How many frames a fast forwarded replay plays for every real frame
+pub const FAST_FORWARD_SPEED: u32 = 4;
+//How far the arrow keys scrub, one second
+pub const SCRUB_FRAMES: i64 = 60;
+
+//A jump somewhere else in the replay, handed to the ReplayPlayer on the next update
+#[derive(Copy, Clone, Debug)]
+pub enum ReplaySeek {
+    Frames(i64),
+    NextMarker(ReplayMarkerKind),
+    PreviousMarker(ReplayMarkerKind)
+}
+
+//The controls for watching a replay
+//P pauses, N steps a single frame while paused, holding F fast forwards
+//Left and Right scrub a second at a time, Up and Down jump to the next and previous round, H and G to the next and previous hit
+pub struct PlaybackControls {
+    pub paused: bool,
+    pub step_requested: bool,
+    pub fast_forward: bool,
+    pub seek_requested: Option<ReplaySeek>
+}
+
+impl PlaybackControls {
+    pub fn new() -> PlaybackControls {
+        PlaybackControls {
+            paused: false,
+            step_requested: false,
+            fast_forward: false,
+            seek_requested: None
+        }
+    }
+
+    pub fn key_down(&mut self, keyboard_button: KeyboardButton) {
+        match keyboard_button {
+            KeyboardButton::P => {
+                self.paused = !self.paused;
+            },
+            KeyboardButton::N => {
+                self.step_requested = true;
+            },
+            KeyboardButton::F => {
+                self.fast_forward = true;
+            },
+            KeyboardButton::Left => {
+                self.seek_requested = Some(ReplaySeek::Frames(-SCRUB_FRAMES));
+            },
+            KeyboardButton::Right => {
+                self.seek_requested = Some(ReplaySeek::Frames(SCRUB_FRAMES));
+            },
+            KeyboardButton::Up => {
+                self.seek_requested = Some(ReplaySeek::NextMarker(ReplayMarkerKind::RoundStart));
+            },
+            KeyboardButton::Down => {
+                self.seek_requested = Some(ReplaySeek::PreviousMarker(ReplayMarkerKind::RoundStart));
+            },
+            KeyboardButton::H => {
+                self.seek_requested = Some(ReplaySeek::NextMarker(ReplayMarkerKind::Hit));
+            },
+            KeyboardButton::G => {
+                self.seek_requested = Some(ReplaySeek::PreviousMarker(ReplayMarkerKind::Hit));
+            },
+            _ => {}
+        }
+    }
+
+    pub fn key_up(&mut self, keyboard_button: KeyboardButton) {
+        match keyboard_button {
+            KeyboardButton::F => {
+                self.fast_forward = false;
+            },
+            _ => {}
+        }
+    }
+
+    //How many frames of the replay to play this frame
+    pub fn frames_to_play(&mut self) -> u32 {
+        if self.paused {
+            if self.step_requested {
+                self.step_requested = false;
+                return 1;
+            }
+            return 0;
+        }
+        if self.fast_forward {
+            return FAST_FORWARD_SPEED;
+        }
+        return 1;
+    }
+
+    pub fn take_seek(&mut self) -> Option<ReplaySeek> {
+        return self.seek_requested.take();
+    }
+}
@@ -20,7 +20,7 @@ pub fn load_character_sprite(animation_library: &AnimationTextureLibrary, charac
 
     sprite_1.atlas = animation_library.get_atlas_for_animation(character.animation_state);
     //And set the texture of the sprite as the subsection of the atlas for the first frame of animation
-    let frame_1 = character.get_current_animation_config();
+    let frame_1 = character.get_current_animation();
     let frame_1 = animation_library.get_atlas_subsection(character.animation_state, frame_1.current_frame);
 
     let sprites_1 = [
@@ -101,7 +101,11 @@ pub fn setup_join_game_button(ctx: &mut Context<FightingApp>, button_texture: Te
 pub struct UI {
     pub backplate: ([Sprite; 1], SpriteShaderPass),
     pub healthbars: ([Sprite; 2], SpriteShaderPass),
-    pub timer_text: (TextShaderPass, TextShader)
+    pub timer_text: (TextShaderPass, TextShader),
+    pub combo_text: (TextShaderPass, TextShader),
+    pub timeline: (Vec<Sprite>, SpriteShaderPass),
+    pub timeline_text: (TextShaderPass, TextShader),
+    pub name_text: (TextShaderPass, TextShader)
 }
 
 
@@ -133,12 +137,51 @@ pub fn setup_health_bars(ctx: &mut Context<FightingApp>) -> ([Sprite; 2], Sprite
     return (health_bars, health_bar_render_pass);
 }
 
+//Where the replay timeline sits, along the bottom of the screen
+pub const TIMELINE_X: f32 = 160.0;
+pub const TIMELINE_Y: f32 = 40.0;
+pub const TIMELINE_WIDTH: f32 = 1120.0;
+pub const TIMELINE_HEIGHT: u16 = 12;
+
+//The bar along the bottom of the screen while watching a replay, the first sprite is the whole replay
+//and the second is how much has been played, the markers for rounds and hits are pushed on after those each frame
+pub fn setup_replay_timeline(ctx: &mut Context<FightingApp>) -> (Vec<Sprite>, SpriteShaderPass) {
+    let mut transform = OrthographicCamera::new(ctx.window_logical_size());
+    transform.set().translation = Vector3::new(-(WIDTH as f32 / 2.0), -(HEIGHT as f32 / 2.0), 0.0);
+    let mut timeline_render_pass = SpriteShaderPass::new(transform.matrix(), ctx);
+    let loaded_texture = Texture::from_png(ctx, GREYSCALE_HEALTH_BAR_GRADIANT, TextureFiltering::none());
+    let first_frame = loaded_texture.subsection(0, loaded_texture.width(), 0, loaded_texture.height());
+    let timeline_sprites = vec![
+        Sprite {
+            pos: Vector3::new(TIMELINE_X, TIMELINE_Y, 0.0),
+            size: Vector2::new(TIMELINE_WIDTH as u16, TIMELINE_HEIGHT),
+            color: RGBA8::BLACK,
+            texture: first_frame,
+            ..Default::default()
+        },
+        Sprite {
+            pos: Vector3::new(TIMELINE_X, TIMELINE_Y, 0.01),
+            size: Vector2::new(0, TIMELINE_HEIGHT),
+            color: RGBA8::GREEN,
+            texture: first_frame,
+            ..Default::default()
+        },
+    ];
+    timeline_render_pass.atlas = loaded_texture;
+    timeline_render_pass.buffer.set_data(&timeline_sprites);
+    return (timeline_sprites, timeline_render_pass);
+}
+
 //Load the sprites for te health bars, and there shader pass
 pub fn setup_ui(ctx: &mut Context<FightingApp>) -> UI {
     UI {
         backplate: setup_ui_backplate(ctx),
         healthbars: setup_health_bars(ctx),
-        timer_text: setup_round_timer_text(ctx)
+        timer_text: setup_round_timer_text(ctx),
+        combo_text: setup_combo_text(ctx),
+        timeline: setup_replay_timeline(ctx),
+        timeline_text: setup_combo_text(ctx), //Starts out empty just like the combo text
+        name_text: setup_combo_text(ctx) //Filled in every frame, the names are not known until the match starts
     }
 }
 
@@ -210,6 +253,18 @@ pub fn setup_round_timer_text(ctx: &mut Context<FightingApp>) -> (TextShaderPass
     return (text_layer, text_shader);
 }
 
+//The text shader pass used for the combo counters, starts empty since no one has a combo yet
+pub fn setup_combo_text(ctx: &mut Context<FightingApp>) -> (TextShaderPass, TextShader) {
+    let mut transform = OrthographicCamera::new(ctx.window_logical_size());
+    transform.set().translation = Vector3::new(-(WIDTH as f32 / 2.0), -(HEIGHT as f32 / 2.0), 0.0);
+    let text_shader = TextShader::new(ctx);
+
+    let mut text_layer = TextShaderPass::new(ctx, transform.matrix());
+    text_layer.set_ortho(transform.matrix());
+
+    return (text_layer, text_shader);
+}
+
 /*
 
 //Load the sprites and the text shader pass used for the timer
@@ -0,0 +1,47 @@
+use ggrs::{Frame, GGRSError, SessionBuilder, SyncTestSession};
+use super::*;
+
+//A GGRS SyncTestSession, every frame it rolls back check_distance frames and re-simulates them,
+//if a re-simulated frame does not hash the same as it did the first time the simulation is not deterministic
+//Both players are fed the local keyboard, there is nobody on the other end
+//The check distance has to stay under the GGRS max prediction window, which is 8 frames by default
+pub struct SyncTest {
+    pub session: SyncTestSession<GGRSConfig>,
+    pub check_distance: usize,
+    pub mismatches: Vec<Frame>,
+    pub error: Option<String> //Set when GGRS fails with anything other than a mismatch, the sync test is over then
+}
+
+impl SyncTest {
+    //Fails if the check distance is not inside the GGRS prediction window
+    pub fn new(check_distance: usize) -> Result<SyncTest, GGRSError> {
+        Ok(SyncTest {
+            session: SyncTest::start_session(check_distance)?,
+            check_distance,
+            mismatches: vec![],
+            error: None
+        })
+    }
+
+    fn start_session(check_distance: usize) -> Result<SyncTestSession<GGRSConfig>, GGRSError> {
+        return SessionBuilder::<GGRSConfig>::new()
+            .with_num_players(2)
+            .with_check_distance(check_distance)
+            .start_synctest_session();
+    }
+
+    pub fn add_local_input(&mut self, input: NetInput) -> Result<(), GGRSError> {
+        for handle in 0..2 {
+            self.session.add_local_input(handle, input)?;
+        }
+        return Ok(());
+    }
+
+    //GGRS keeps failing on the same frame once it has found a mismatch, so the session is started over
+    //from wherever the Round is now, that way every mismatch gets reported and not just the first one
+    pub fn record_mismatch(&mut self, frame: Frame) -> Result<(), GGRSError> {
+        self.mismatches.push(frame);
+        self.session = SyncTest::start_session(self.check_distance)?;
+        return Ok(());
+    }
+}
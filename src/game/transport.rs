@@ -0,0 +1,111 @@
+use ggrs::{Message, NonBlockingSocket, PlayerType};
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use super::*;
+
+//Whatever carries the packets between the players, Net and GGRS only ever see a Transport
+//
+//A transport finds the other player, however it does that, and then moves packets to and from them
+//udp_transport.rs talks straight to addresses it is given, like the ones from --players, cupid_transport.rs asks Cupid who to talk to and then does the same,
+//matchbox_transport.rs goes through the matchbox server and WebRTC, which is the only one that works in the browser
+
+//The first byte of every packet says who it is for
+const GGRS_PACKET: u8 = 0;
+const CHECKSUM_PACKET: u8 = 1;
+
+//The GGRS address of another player, each transport has its own way of telling them apart
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PeerAddress {
+    Udp(SocketAddr),
+    WebRtc(String) //The matchbox peer id
+}
+
+//Someone in the match, in GGRS player handle order
+#[derive(Clone, Debug)]
+pub struct MatchPlayer {
+    pub player_type: PlayerType<PeerAddress>,
+    pub profile: PlayerProfile
+}
+
+//How the game finds the other player
+#[derive(Clone, Debug)]
+pub enum Matchmaker {
+    Cupid(String), //The address Cupid is running on, the match is then played over UDP, not in the browser
+    Matchbox(String), //The url of the matchbox server, the match is then played over WebRTC
+    Direct { //No matchmaking, everyone's address is already known and the match is played over UDP, not in the browser
+        local_port: u16,
+        players: Vec<String>, //In GGRS player handle order, "localhost" or an address
+        spectators: Vec<String>
+    }
+}
+
+pub trait Transport: Send {
+    //Called every frame until everyone is connected, then returns who is playing in GGRS player handle order
+    //An error means the match is never going to happen, like the matchmaker turning us away
+    fn poll_players(&mut self) -> Result<Option<Vec<MatchPlayer>>, String>;
+    fn send_to(&mut self, packet: &[u8], address: &PeerAddress);
+    //An error means the connection is gone for good, the match can not go on
+    fn receive_all(&mut self) -> Result<Vec<(PeerAddress, Vec<u8>)>, String>;
+}
+
+//What GGRS is given as its socket, it shares the transport with Net so our checksum reports
+//can go through the same connection as the game traffic does
+pub struct TransportSocket {
+    pub transport: Arc<Mutex<Box<dyn Transport>>>,
+    pub received_checksums: Arc<Mutex<Vec<ChecksumReport>>>,
+    pub error: Arc<Mutex<Option<String>>> //GGRS has no way to be told the transport broke, so Net picks it up from here
+}
+
+impl TransportSocket {
+    pub fn new(transport: Arc<Mutex<Box<dyn Transport>>>) -> TransportSocket {
+        TransportSocket {
+            transport,
+            received_checksums: Arc::new(Mutex::new(vec![])),
+            error: Arc::new(Mutex::new(None))
+        }
+    }
+
+    //Checksum reports are sent by Net outside of GGRS, this tags them so the other side's TransportSocket can pick them out
+    pub fn checksum_packet(report: &ChecksumReport) -> Vec<u8> {
+        let mut packet = vec![CHECKSUM_PACKET];
+        bincode::serialize_into(&mut packet, report).unwrap();
+        return packet;
+    }
+}
+
+impl NonBlockingSocket<PeerAddress> for TransportSocket {
+    fn send_to(&mut self, msg: &Message, addr: &PeerAddress) {
+        let mut packet = vec![GGRS_PACKET];
+        bincode::serialize_into(&mut packet, msg).unwrap();
+        self.transport.lock().unwrap().send_to(&packet, addr);
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(PeerAddress, Message)> {
+        let mut received_messages = vec![];
+        let packets = match self.transport.lock().unwrap().receive_all() {
+            Ok(packets) => packets,
+            Err(reason) => {
+                *self.error.lock().unwrap() = Some(reason);
+                return received_messages;
+            }
+        };
+        for (address, packet) in packets {
+            if packet.len() == 0 {
+                continue;
+            }
+            let payload = &packet[1..];
+            if packet[0] == GGRS_PACKET {
+                if let Ok(msg) = bincode::deserialize(payload) {
+                    received_messages.push((address, msg));
+                }
+            }
+            else if packet[0] == CHECKSUM_PACKET {
+                if let Ok(report) = bincode::deserialize(payload) {
+                    self.received_checksums.lock().unwrap().push(report);
+                }
+            }
+        }
+        return received_messages;
+    }
+}
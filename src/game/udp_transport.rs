@@ -0,0 +1,88 @@
+use ggrs::PlayerType;
+
+use std::io::ErrorKind;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use super::*;
+
+const RECEIVE_BUFFER_SIZE: usize = 4096;
+
+//Plain UDP to addresses we already know, Cupid hands us these, or they are typed in on the command line
+pub struct UdpTransport {
+    socket: UdpSocket,
+    players: Option<Vec<MatchPlayer>>, //Known from the start, handed over the first time we are polled
+    buffer: [u8; RECEIVE_BUFFER_SIZE]
+}
+
+impl UdpTransport {
+    pub fn bind_to_port(port: u16, players: Vec<MatchPlayer>) -> std::io::Result<UdpTransport> {
+        let socket = UdpSocket::bind(SocketAddr::from(([0, 0, 0, 0], port)))?;
+        socket.set_nonblocking(true)?;
+        Ok(UdpTransport {
+            socket,
+            players: Some(players),
+            buffer: [0; RECEIVE_BUFFER_SIZE]
+        })
+    }
+
+    //For LAN sessions without Cupid, the players are given in GGRS player handle order, "localhost" is us and the rest
+    //are addresses, spectators are sent the match as it is played
+    pub fn direct(local_port: u16, players: &[String], spectators: &[String], profile: PlayerProfile) -> Result<UdpTransport, String> {
+        if players.len() != 2 {
+            return Err(format!("a match has 2 players but {} were given", players.len()));
+        }
+        if players.iter().filter(|x| *x == "localhost").count() != 1 {
+            return Err(String::from("exactly one of the players has to be localhost"));
+        }
+
+        let mut match_players = vec![];
+        for (handle, player) in players.iter().enumerate() {
+            if player == "localhost" {
+                match_players.push(MatchPlayer { player_type: PlayerType::Local, profile: profile.clone() });
+            }
+            else {
+                //Nobody swaps profiles without Cupid, so the other player goes by their slot and is recorded with the first pallete
+                let address = PeerAddress::Udp(resolve(player)?);
+                match_players.push(MatchPlayer { player_type: PlayerType::Remote(address), profile: PlayerProfile::new(&format!("Player {}", handle + 1), 0) });
+            }
+        }
+        for spectator in spectators.iter() {
+            let address = PeerAddress::Udp(resolve(spectator)?);
+            match_players.push(MatchPlayer { player_type: PlayerType::Spectator(address), profile: PlayerProfile::new("Spectator", 0) });
+        }
+
+        return UdpTransport::bind_to_port(local_port, match_players).map_err(|x| format!("can not bind port {}: {}", local_port, x));
+    }
+}
+
+//Takes an ip and port, or a host name and port for machines on the LAN
+fn resolve(address: &str) -> Result<SocketAddr, String> {
+    let mut addresses = address.to_socket_addrs().map_err(|x| format!("{} is not an address: {}", address, x))?;
+    return addresses.next().ok_or(format!("{} did not resolve to an address", address));
+}
+
+impl Transport for UdpTransport {
+    fn poll_players(&mut self) -> Result<Option<Vec<MatchPlayer>>, String> {
+        return Ok(self.players.take());
+    }
+
+    fn send_to(&mut self, packet: &[u8], address: &PeerAddress) {
+        if let PeerAddress::Udp(address) = address {
+            let _ = self.socket.send_to(packet, address);
+        }
+    }
+
+    fn receive_all(&mut self) -> Result<Vec<(PeerAddress, Vec<u8>)>, String> {
+        let mut received = vec![];
+        loop {
+            match self.socket.recv_from(&mut self.buffer) {
+                Ok((size, address)) => {
+                    received.push((PeerAddress::Udp(address), self.buffer[..size].to_vec()));
+                },
+                Err(ref err) if err.kind() == ErrorKind::WouldBlock => return Ok(received),
+                //Windows reports an ICMP port unreachable from an earlier send this way, it is safe to ignore
+                Err(ref err) if err.kind() == ErrorKind::ConnectionReset => continue,
+                Err(err) => return Err(format!("the UDP socket stopped working: {}", err))
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! The gameplay core of Storm Fighting.
+//!
+//! Everything in here runs without a window, a GPU or a network session, so rounds
+//! can be simulated headlessly by feeding `NetInput`s straight into `Round::advance`.
+//! The Cupid matchmaking protocol lives here too, so the game and the server agree on it.
+
+mod simulation;
+mod cupid;
+
+pub use simulation::*;
+pub use cupid::*;
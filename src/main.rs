@@ -3,11 +3,10 @@ extern crate structopt;
 
 use core::convert::From;
 use core::time::Duration;
-use std::{env, ffi::OsString, fs::{self, DirEntry}, path::Path};
+use std::{env, ffi::OsString, fs::{self, DirEntry}, path::{Path, PathBuf}};
 use std::fs::File;
 use std::io::{self, BufRead};
 
-use hashbrown::HashMap;
 use simplelog::*;
 use storm::*;
 use storm::event::*;
@@ -49,12 +48,79 @@ pub enum GameState {
 }
 
 // Example: cargo run --release -- -c 127.0.0.1
+// Example without Cupid: cargo run --release -- --local-port 7000 --players localhost 127.0.0.1:7001
 #[derive(Debug, StructOpt)]
 #[structopt(name = "command_line_args", about = "Command line arguments for Storm Fighting.")]
 struct Opt {
     #[structopt(short = "c", long, default_value="127.0.0.1",
     help="IP Address where Cupid is running.")]
     cupid_ip_addr: String,
+    #[structopt(short = "m", long,
+    help="Find the match through the matchbox server at this url instead of Cupid, and play it over WebRTC so browser players can join. The browser build always does this, with ws://127.0.0.1:3536 if no url is given.")]
+    matchbox: Option<String>,
+    #[structopt(long, requires = "players", conflicts_with_all = &["matchbox", "create-room", "join-room"],
+    help="Skip matchmaking and play the --players directly over UDP from this port.")]
+    local_port: Option<u16>,
+    #[structopt(long, requires = "local-port",
+    help="The players in order, localhost for yourself and ip:port for the other player, e.g. --players localhost 127.0.0.1:7001")]
+    players: Vec<String>,
+    #[structopt(long, requires = "players",
+    help="Addresses to send the match to as it is played, only with --players.")]
+    spectators: Vec<String>,
+    #[structopt(long, conflicts_with = "join-room",
+    help="Make a private room, its code is printed for the other player to join with.")]
+    create_room: bool,
+    #[structopt(long,
+    help="Join the private room with this code instead of playing whoever is waiting.")]
+    join_room: Option<String>,
+    #[structopt(short = "n", long, default_value="Player",
+    help="The name the other player sees above your health bar.")]
+    name: String,
+    #[structopt(short = "r", long, parse(from_os_str),
+    help="Watch a recorded replay instead of joining a match. P pauses, N steps a frame, hold F to fast forward, Left and Right scrub, Up and Down jump between rounds, G and H between hits.")]
+    replay: Option<PathBuf>,
+    #[structopt(long,
+    help="Run a GGRS sync test instead of joining a match, the keyboard drives both players and any frame that re-simulates differently is reported.")]
+    sync_test: bool,
+    #[structopt(long, default_value="7",
+    help="How many frames the sync test rolls back every frame, has to be less than 8.")]
+    check_distance: usize,
+}
+
+impl Opt {
+    fn matchmaker(&self) -> Matchmaker {
+        if let Some(local_port) = self.local_port {
+            return Matchmaker::Direct {
+                local_port,
+                players: self.players.clone(),
+                spectators: self.spectators.clone()
+            };
+        }
+        if let Some(matchbox_url) = &self.matchbox {
+            return Matchmaker::Matchbox(matchbox_url.clone());
+        }
+        if cfg!(target_arch = "wasm32") {
+            return Matchmaker::Matchbox(String::from(MATCHBOX_URL));
+        }
+        return Matchmaker::Cupid(self.cupid_ip_addr.clone());
+    }
+
+    fn match_request(&self) -> MatchRequest {
+        if self.create_room {
+            return MatchRequest::CreateRoom;
+        }
+        match &self.join_room {
+            Some(code) => return MatchRequest::JoinRoom { code: code.clone() },
+            None => return MatchRequest::FindMatch
+        }
+    }
+}
+
+//Loads a replay and checks it was recorded with the same character sheet we are running
+fn load_replay_player(path: &Path, gameplay_config: &GameplayConfig) -> Result<ReplayPlayer, String> {
+    let bytes = fs::read(path).map_err(|x| format!("{}: {}", path.display(), x))?;
+    let replay: Replay = bincode::deserialize(&bytes).map_err(|x| format!("{}: {}", path.display(), x))?;
+    return ReplayPlayer::new(replay, gameplay_config);
 }
 
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
@@ -115,6 +181,23 @@ pub struct FightingApp {
     pub menu: Option<Menu>
 }
 
+impl FightingApp {
+    fn save_replay(&self) {
+        if let Some(game) = self.game.as_ref() {
+            game.save_replay();
+        }
+    }
+
+    //The match could not be started or the connection broke, so the player is put back on the menu to try again
+    fn return_to_menu(&mut self, reason: String) {
+        println!("Back to the menu: {}", reason);
+        self.save_replay();
+        self.game = None;
+        self.menu.as_mut().unwrap().show_error(reason);
+        self.game_state = GameState::Menu;
+    }
+}
+
 impl App for FightingApp {
     fn new(ctx: &mut Context<Self>) -> Self {
         ctx.wait_periodic(Some(Duration::from_secs_f32(1.0 / 60.0)));
@@ -133,6 +216,7 @@ impl App for FightingApp {
     }
 
     fn on_close_requested(&mut self, ctx: &mut Context<Self>) {
+        self.save_replay();
         ctx.request_stop();
     }
 
@@ -141,6 +225,9 @@ impl App for FightingApp {
             GameState::Game => {
                 if self.game.is_some() {
                     self.game.as_mut().unwrap().update(ctx);
+                    if let Some(reason) = self.game.as_ref().unwrap().failure() {
+                        self.return_to_menu(reason);
+                    }
                 }
             },
             GameState::Menu => {
@@ -148,6 +235,7 @@ impl App for FightingApp {
                 if desired_game_state != GameState::Menu && self.transitioning == false {
                     self.game_state = desired_game_state;
                     self.transitioning = true;
+                    let match_type = self.menu.as_ref().unwrap().selected_match_type;
 
                     ctx.read(&[String::from(RESOURCE_PATH) + &String::from("ryu_character_sheet.json")], move |ctx, _app, assets|{
                         for asset in assets {
@@ -177,71 +265,53 @@ impl App for FightingApp {
                                         }
                                         app.transitioning = false;
 
-                                        let mut animation_for_character_state_library = HashMap::new();
-                                        animation_for_character_state_library.insert(CharacterState::Idle, AnimationStateForCharacterState::new(AnimationState::Crouched, AnimationState::Idle, AnimationState::Idle));
-                                        animation_for_character_state_library.insert(CharacterState::ForwardRun, AnimationStateForCharacterState::new(AnimationState::ForwardRun, AnimationState::ForwardRun, AnimationState::ForwardRun));
-                                        animation_for_character_state_library.insert(CharacterState::BackwardRun, AnimationStateForCharacterState::new(AnimationState::BackwardRun, AnimationState::BackwardRun, AnimationState::BackwardRun));
-                                        animation_for_character_state_library.insert(CharacterState::LightHitRecovery, AnimationStateForCharacterState::new(AnimationState::LightHitRecovery, AnimationState::LightHitRecovery, AnimationState::LightHitRecovery));
-                                        animation_for_character_state_library.insert(CharacterState::Blocking, AnimationStateForCharacterState::new(AnimationState::Blocking, AnimationState::Blocking, AnimationState::Blocking));
-                                        animation_for_character_state_library.insert(CharacterState::Crouching, AnimationStateForCharacterState::new(AnimationState::Crouching, AnimationState::Crouching, AnimationState::Crouching));
-                                        animation_for_character_state_library.insert(CharacterState::LightAttack, AnimationStateForCharacterState::new(AnimationState::LightCrouchAttack, AnimationState::LightAttack, AnimationState::LightJumpingKick));
-                                        animation_for_character_state_library.insert(CharacterState::MediumAttack, AnimationStateForCharacterState::new(AnimationState::LightCrouchAttack, AnimationState::MediumAttack, AnimationState::MediumAttack));
-                                        animation_for_character_state_library.insert(CharacterState::HeavyAttack, AnimationStateForCharacterState::new(AnimationState::HeavyCrouchingAttack, AnimationState::HeavyAttack, AnimationState::HeavyAttack));
-                                        animation_for_character_state_library.insert(CharacterState::LightKick, AnimationStateForCharacterState::new(AnimationState::LightCrouchKick, AnimationState::LightKick, AnimationState::LightKick));
-                                        animation_for_character_state_library.insert(CharacterState::MediumKick, AnimationStateForCharacterState::new(AnimationState::MediumCrouchKick, AnimationState::MediumKick, AnimationState::MediumKick));
-                                        animation_for_character_state_library.insert(CharacterState::HeavyKick, AnimationStateForCharacterState::new(AnimationState::HeavyCrouchKick, AnimationState::HeavyKick, AnimationState::HeavyKick));
-                                        animation_for_character_state_library.insert(CharacterState::ForwardDash, AnimationStateForCharacterState::new(AnimationState::ForwardDash, AnimationState::ForwardDash, AnimationState::ForwardDash));
-                                        animation_for_character_state_library.insert(CharacterState::BackwardDash, AnimationStateForCharacterState::new(AnimationState::BackwardDash, AnimationState::BackwardDash, AnimationState::BackwardDash));
-                                        animation_for_character_state_library.insert(CharacterState::Special1, AnimationStateForCharacterState::new(AnimationState::Special1, AnimationState::Special1, AnimationState::Special1));
-                                        animation_for_character_state_library.insert(CharacterState::Won, AnimationStateForCharacterState::new(AnimationState::Won, AnimationState::Won, AnimationState::Won));
-                                        animation_for_character_state_library.insert(CharacterState::Lost, AnimationStateForCharacterState::new(AnimationState::Lost, AnimationState::Lost, AnimationState::Lost));
-                                        animation_for_character_state_library.insert(CharacterState::Jump, AnimationStateForCharacterState::new(AnimationState::Jump, AnimationState::Jump, AnimationState::Jump));
-                                        animation_for_character_state_library.insert(CharacterState::Parry, AnimationStateForCharacterState::new(AnimationState::Parry, AnimationState::Parry, AnimationState::Parry));
-                                        animation_for_character_state_library.insert(CharacterState::Parried, AnimationStateForCharacterState::new(AnimationState::LightHitRecovery, AnimationState::LightHitRecovery, AnimationState::LightHitRecovery));
-                                        animation_for_character_state_library.insert(CharacterState::ForwardJump, AnimationStateForCharacterState::new(AnimationState::ForwardJump, AnimationState::ForwardJump, AnimationState::ForwardJump));
-                                        animation_for_character_state_library.insert(CharacterState::Dizzie, AnimationStateForCharacterState::new(AnimationState::Dizzie, AnimationState::Dizzie, AnimationState::Dizzie));
-
-                                        let animation_state = vec![
-                                            AnimationState::Idle,
-                                            AnimationState::ForwardRun,
-                                            AnimationState::BackwardRun,
-                                            AnimationState::LightAttack,
-                                            AnimationState::MediumAttack,
-                                            AnimationState::HeavyAttack,
-                                            AnimationState::LightHitRecovery,
-                                            AnimationState::Crouched,
-                                            AnimationState::Crouching,
-                                            AnimationState::Blocking,
-                                            AnimationState::LightCrouchAttack,
-                                            AnimationState::HeavyCrouchingAttack,
-                                            AnimationState::LightKick,
-                                            AnimationState::MediumKick,
-                                            AnimationState::HeavyKick,
-                                            AnimationState::ForwardDash,
-                                            AnimationState::BackwardDash,
-                                            AnimationState::Special1,
-                                            AnimationState::Won,
-                                            AnimationState::Lost,
-                                            AnimationState::Jump,
-                                            AnimationState::Parry,
-                                            AnimationState::ForwardJump,
-                                            AnimationState::LightCrouchKick,
-                                            AnimationState::MediumCrouchKick,
-                                            AnimationState::HeavyCrouchKick
-                                        ];
-
-                                        let mut animation_configs = HashMap::new();
-                                        for state in animation_state {
-                                            animation_configs.insert(state, AnimationConfig::new(character_sheet.animations.get(&state.to_string()).unwrap().frame_lengths.clone()));
-                                        }
                                         let mut pallete : [cgmath::Vector3<f32>; 256] = [cgmath::Vector3::<f32>::new(0.0, 0.0, 0.0);256];
+                                        let pallete_choice = 0;
                                         let test = read_palletes();
                                         for i in 0..256 {
-                                            pallete[i] = test[0][i];
+                                            pallete[i] = test[pallete_choice][i];
+                                        }
+                                        let gameplay_config = match GameplayConfig::new_from_sheet(character_sheet.clone()) {
+                                            Ok(gameplay_config) => gameplay_config,
+                                            Err(e) => {
+                                                app.return_to_menu(format!("can not use the character sheet: {}", e));
+                                                return;
+                                            }
+                                        };
+                                        let opt = Opt::from_args();
+                                        let game_config = GameConfig::new(gameplay_config, animation_texture_library, pallete, pallete_choice);
+                                        if opt.sync_test {
+                                            let sync_test = match SyncTest::new(opt.check_distance) {
+                                                Ok(sync_test) => sync_test,
+                                                Err(e) => {
+                                                    app.return_to_menu(format!("can not start the sync test with --check-distance {}: {:?}", opt.check_distance, e));
+                                                    return;
+                                                }
+                                            };
+                                            app.game = Some(Game::load_sync_test(ctx, game_config, sync_test));
+                                        }
+                                        else if let Some(replay_path) = opt.replay {
+                                            let replay_player = match load_replay_player(&replay_path, &game_config.gameplay_config) {
+                                                Ok(replay_player) => replay_player,
+                                                Err(e) => {
+                                                    app.return_to_menu(format!("can not play this replay: {}", e));
+                                                    return;
+                                                }
+                                            };
+                                            app.game = Some(Game::load_replay(ctx, game_config, replay_player));
+                                        }
+                                        else if match_type == MatchType::LocalVersus {
+                                            app.game = Some(Game::load_local_versus(ctx, game_config));
+                                        }
+                                        else {
+                                            match Game::load_game_with_config(ctx, game_config, opt.matchmaker(), opt.match_request(), PlayerProfile::new(&opt.name, pallete_choice)) {
+                                                Ok(game) => app.game = Some(game),
+                                                Err(reason) => {
+                                                    app.return_to_menu(reason);
+                                                    return;
+                                                }
+                                            }
                                         }
-                                        let game_config = GameConfig::new(CollisionLibrary::new_from_sheet(&character_sheet), ComboLibrary::default(), animation_texture_library, animation_for_character_state_library, animation_configs, character_sheet.clone(), pallete);
-
-                                        app.game = Some(Game::load_game_with_config(ctx, game_config, &Opt::from_args().cupid_ip_addr));
                                         app.game_state = GameState::Game;
                                     });
                                 },
@@ -260,11 +330,17 @@ impl App for FightingApp {
 
     fn on_key_pressed(&mut self, ctx: &mut Context<Self>, key: event::KeyboardButton, _is_repeat: bool) {
         match key {
-            KeyboardButton::Escape => ctx.request_stop(),
+            KeyboardButton::Escape => {
+                self.save_replay();
+                ctx.request_stop();
+            },
             _ => {
                 if self.game.is_some() {
                     self.game.as_mut().unwrap().key_down(key);
                 }
+                else if self.game_state == GameState::Menu {
+                    self.menu.as_mut().unwrap().key_down(key);
+                }
             }
         }
     }
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+pub const FRAME_HEIGHT: u32 =  178;
+pub const FRAME_WIDTH: u32 =  290;
+
+#[macro_export]
+macro_rules! animation_state_enum {
+    ( $( $x:ident ),* ) => {
+         #[derive(Eq, PartialEq, Hash, Debug, Serialize, Deserialize, Copy, Clone)]
+          pub enum AnimationState {
+            $(
+                $x,
+            )*
+          }
+
+          impl AnimationState {
+            pub fn from_string(value:&String) -> AnimationState {
+              $(
+                if value == stringify!($x) {
+                  return AnimationState::$x;
+                }
+              )*
+                      panic!("{:?} is an unknow animation state", value);
+            }
+
+            pub fn to_string(&self) -> String {
+              match *self {
+                $(
+                AnimationState::$x => {
+                  return String::from(stringify!($x));
+                }
+                )*
+              }
+            }
+          }
+    };
+}
+
+
+animation_state_enum!(    Idle,
+    ForwardRun,
+    BackwardRun,
+    LightAttack,
+    MediumAttack,
+    HeavyAttack,
+    LightHitRecovery,
+    Crouched,
+    Crouching,
+    Blocking,
+    LightCrouchAttack,
+    HeavyCrouchingAttack,
+    LightCrouchKick,
+    MediumCrouchKick,
+    HeavyCrouchKick,
+    LightKick,
+    MediumKick,
+    HeavyKick,
+    ForwardDash,
+    BackwardDash,
+    Special1,
+    Won,
+    Lost,
+    Jump,
+    Parry,
+    ForwardJump,
+    LightJumpingKick,
+    JumpingLightPunch,
+    Dizzie,
+    Throw,
+    Thrown,
+    AirHit,
+    KnockedDown,
+    WakeUp);
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone)]
+//A frame number based timer for sprites IE: Does not use delta timer/real time it is an monotonic timer
+pub struct SpriteTimer {
+    pub total_frames: u32,
+    pub current_frame: u32,
+    pub finished: bool,
+}
+
+impl SpriteTimer {
+    pub fn new(total_frames: u32) -> SpriteTimer {
+        SpriteTimer {
+            total_frames,
+            current_frame: 0,
+            finished: false,
+        }
+    }
+
+    //How you advance the timer
+    pub fn tick(&mut self) {
+        self.finished = false;
+        self.current_frame += 1;
+        if self.current_frame == self.total_frames {
+            self.finished = true;
+            self.current_frame = 0;
+        }
+    }
+
+    //Call to see if this timer has finished it timer
+    pub fn finished(&mut self) -> bool {
+        self.finished
+    }
+
+    //Resets the state of the timer, but not total_frames
+    pub fn reset(&mut self) {
+        self.current_frame = 0;
+        self.finished = false;
+}
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+//The static half of an animation, how long each frame lasts
+//It lives in the GameplayConfig and is never copied into the Round
+pub struct AnimationConfig {
+    pub frame_times: Vec<u8>
+}
+
+impl AnimationConfig {
+    pub fn new(frame_times: Vec<u8>) -> AnimationConfig {
+        AnimationConfig {
+            frame_times
+        }
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        return self.frame_times.len() as u32;
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone)]
+//The rolled back half of an animation, which frame we are on
+//The animation itself is referenced by the AnimationState of whoever is playing it
+pub struct AnimationPlayer {
+    pub sprite_timer: SpriteTimer, //The number of monotonic frames a single frame will take
+    pub current_frame: u32, //The current frame we are on
+}
+
+impl AnimationPlayer {
+    pub fn new(frame_length: u32) -> AnimationPlayer {
+        AnimationPlayer {
+            sprite_timer: SpriteTimer::new(frame_length),
+            current_frame: 0
+        }
+    }
+
+    //Starts playing the animation from its first frame
+    pub fn from_config(animation_config: &AnimationConfig) -> AnimationPlayer {
+        return AnimationPlayer::new(animation_config.frame_times[0] as u32);
+    }
+
+    pub fn advance_to_next_frame(&mut self, animation_config: &AnimationConfig) {
+        self.sprite_timer = SpriteTimer::new(animation_config.frame_times[self.current_frame as usize] as u32);
+    }
+
+    pub fn reset(&mut self) {
+        self.current_frame = 0;
+        self.sprite_timer.reset();
+    }
+
+    pub fn is_done(&self, frame_count: u32) -> bool {
+        return self.current_frame == frame_count;
+    }
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> AnimationPlayer {
+        return AnimationPlayer::new(1);
+    }
+}
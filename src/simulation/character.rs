@@ -0,0 +1,475 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+pub const CHARACTER_X_SPEED : Fixed = Fixed::from_int(5);
+pub const FRAME_HISTORY_LENGTH: usize = 30;
+//How many frames the thrown character has to press throw to break out of it
+pub const THROW_TECH_WINDOW: u32 = 7;
+//How many hits a character can take in the air before they fall out of the combo
+pub const JUGGLE_LIMIT: u32 = 3;
+//How long a character stays on the ground after a knockdown, quick and delayed rise change this
+pub const KNOCKDOWN_FRAMES: u32 = 40;
+pub const QUICK_RISE_FRAMES: u32 = 15;
+pub const DELAYED_RISE_FRAMES: u32 = 20;
+//Where the walk box sits inside of a frame
+pub const WALK_BOX_OFFSET_X : i32 = 131;
+pub const WALK_BOX_OFFSET_Y : i32 = 57;
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone)]
+pub enum ScreenSide {
+    Left,
+    Right
+}
+
+impl ScreenSide {
+    pub fn direction(&self) -> Fixed {
+        match self {
+            &ScreenSide::Left => {
+                -Fixed::ONE
+            }
+            &ScreenSide::Right => {
+                Fixed::ONE
+            }
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone, Debug)]
+pub enum CharacterState {
+    Idle,
+    ForwardRun,
+    BackwardRun,
+    LightAttack,
+    MediumAttack,
+    HeavyAttack,
+    LightHitRecovery,
+    MediumHitRecovery,
+    Blocking,
+    Crouching,
+    LightKick,
+    MediumKick,
+    HeavyKick,
+    ForwardDash,
+    BackwardDash,
+    Special1,
+    Won,
+    Lost,
+    Jump,
+    Parry,
+    Parried,
+    ForwardJump,
+    Dizzie,
+    Throwing,
+    Thrown,
+    AirHit,
+    KnockedDown,
+    WakeUp,
+}
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone, Debug)]
+pub enum CharacterAction {
+    None,
+    MoveForward,
+    MoveBackward,
+    DashForward,
+    DashBackward,
+    LightAttack,
+    MediumAttack,
+    HeavyAttack,
+    LightKick,
+    MediumKick,
+    HeavyKick,
+    Crouch,
+    Special1,
+    Jump,
+    Parry,
+    ForwardJump,
+    Throw
+}
+
+//If the move the character is doing has connected with the other character yet
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone, Debug)]
+pub enum MoveContact {
+    None,
+    Hit,
+    Block
+}
+
+impl CharacterAction {
+    //The state a character goes into when they start an attack with this action, None if it is not an attack
+    pub fn attack_state(&self) -> Option<CharacterState> {
+        match self {
+            CharacterAction::LightAttack => Some(CharacterState::LightAttack),
+            CharacterAction::MediumAttack => Some(CharacterState::MediumAttack),
+            CharacterAction::HeavyAttack => Some(CharacterState::HeavyAttack),
+            CharacterAction::LightKick => Some(CharacterState::LightKick),
+            CharacterAction::MediumKick => Some(CharacterState::MediumKick),
+            CharacterAction::HeavyKick => Some(CharacterState::HeavyKick),
+            CharacterAction::Special1 => Some(CharacterState::Special1),
+            CharacterAction::Parry => Some(CharacterState::Parry),
+            CharacterAction::Throw => Some(CharacterState::Throwing),
+            _ => None
+        }
+    }
+
+    //If the button behind this action went down on this frame, pressed only has the buttons that did
+    pub fn is_pressed(&self, pressed: &ScreenSideAdjustedInput) -> bool {
+        match self {
+            CharacterAction::LightAttack => pressed.light_attack,
+            CharacterAction::MediumAttack => pressed.medium_attack,
+            CharacterAction::HeavyAttack => pressed.heavy_attack,
+            CharacterAction::LightKick => pressed.light_kick,
+            CharacterAction::MediumKick => pressed.medium_kick,
+            CharacterAction::HeavyKick => pressed.heavy_kick,
+            CharacterAction::Throw => pressed.throw,
+            //Motions say which button finishes them in the sheet, any button going down on the frame they come out is enough
+            _ => pressed.light_attack || pressed.medium_attack || pressed.heavy_attack
+                || pressed.light_kick || pressed.medium_kick || pressed.heavy_kick
+        }
+    }
+}
+
+pub struct AnimationStateForCharacterState {
+    pub crouched: AnimationState,
+    pub standing: AnimationState,
+    pub jumping: AnimationState
+}
+
+impl AnimationStateForCharacterState {
+    pub fn new(crouched: AnimationState, standing: AnimationState, jumping: AnimationState) -> AnimationStateForCharacterState {
+        AnimationStateForCharacterState {
+            crouched,
+            standing,
+            jumping
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone)]
+pub struct Character {
+    pub animation_state: AnimationState, //The characters current animation it is playing
+    pub character_state: CharacterState, //The current character states
+    pub current_animation: AnimationPlayer, //Where we are in the animation for animation_state, the frame data itself stays in the config
+    pub character_position: FixedVector2, //Where in the world it is
+    pub character_velocity: FixedVector2, //How far it wants to move this frame
+    pub screen_side: ScreenSide, //Which side of the screen it is on
+    pub health: u32, //How much health it has
+    pub stun: u32,
+    pub is_crouched: bool, //Is character crouched at the moment, used so we don't have a set of "crouched" states
+    pub is_jumping: bool,
+    pub input_recognizer: InputRecognizer, //Everything needed to pick motions out of the inputs, rolled back with the rest of the character
+    pub done: bool,
+    pub move_starting_screen_side: Fixed,
+    pub stun_bar: u32,
+    pub recovery_frames: u32, //How many more frames of hitstun or blockstun the character has left
+    pub pushback: Fixed, //How far the character gets pushed each frame while in hitstun or blockstun
+    pub throw_tech_frames: u32, //While thrown, or throwing someone, how many more frames the throw has left to be teched
+    pub hit_registry: HitRegistry, //Who the current move has already hit
+    pub juggle_count: u32, //How many hits the character has taken since they were sent into the air
+    pub rise_chosen: bool, //If the character has already picked a quick or delayed rise for this knockdown
+    pub move_contact: MoveContact //If the current move has hit or been blocked, used to open cancel windows
+}
+
+impl Character {
+    pub fn new(screen_side: ScreenSide) -> Character {
+        Character {
+            animation_state: AnimationState::Idle,
+            character_state: CharacterState::Idle,
+            current_animation: AnimationPlayer::default(),
+            character_position: FixedVector2::zero(),
+            character_velocity: FixedVector2::zero(),
+            screen_side,
+            health: 250,
+            stun: 0,
+            is_crouched: false,
+            is_jumping: false,
+            input_recognizer: InputRecognizer::new(),
+            done: false,
+            move_starting_screen_side: Fixed::ZERO,
+            stun_bar: 0,
+            recovery_frames: 0,
+            pushback: Fixed::ZERO,
+            throw_tech_frames: 0,
+            hit_registry: HitRegistry::new(),
+            juggle_count: 0,
+            rise_chosen: false,
+            move_contact: MoveContact::None
+        }
+    }
+
+    pub fn set_character_state(&mut self, new_state: CharacterState, game_config: &GameplayConfig) {
+
+        self.character_state = new_state;
+        let animation_state;
+        if self.is_crouched {
+            animation_state = game_config.animation_for_character_state_library.get(&self.character_state).unwrap().crouched;
+        }
+        else {
+            animation_state = game_config.animation_for_character_state_library.get(&self.character_state).unwrap().standing;
+        }
+        self.current_animation = AnimationPlayer::from_config(game_config.get_animation_config(animation_state));
+        self.set_animation_state(animation_state);
+        //Every new state is a new activation, so nothing has been hit by it yet
+        self.hit_registry.reset();
+        self.move_contact = MoveContact::None;
+    }
+
+    pub fn set_move_starting_screen_side(&mut self, screen_side: Fixed) {
+        self.move_starting_screen_side = screen_side;
+    }
+
+    pub fn finished_animation_whats_next(&mut self) -> CharacterState {
+        //This is a State machine that controls which states a character
+        //can move between
+        match self.character_state {
+            CharacterState::ForwardRun => {
+                CharacterState::ForwardRun
+            },
+            CharacterState::BackwardRun => {
+                CharacterState::BackwardRun
+            },
+            //Hit and block recovery loop until the stun from the move runs out
+            CharacterState::LightHitRecovery | CharacterState::Blocking if self.recovery_frames > 0 => {
+                self.character_state
+            },
+            //Characters in the air or on the ground stay there until they land or get up
+            CharacterState::AirHit => {
+                CharacterState::AirHit
+            },
+            CharacterState::KnockedDown if self.recovery_frames > 0 => {
+                CharacterState::KnockedDown
+            },
+            //Both sides of a throw hold until it lands or is teched, however long the animations are
+            CharacterState::Throwing | CharacterState::Thrown if self.throw_tech_frames > 0 => {
+                self.character_state
+            },
+            _ => {
+                CharacterState::Idle
+            }
+        }
+    }
+
+    pub fn set_animation_state(&mut self, new_state: AnimationState) {
+        //Reset the old animation struct before we move onto the new one
+        self.animation_state = new_state;
+    }
+
+    pub fn get_current_animation(&self) -> AnimationPlayer {
+        return self.current_animation;
+    }
+    
+    pub fn process_new_input(&mut self, frame_input: ScreenSideAdjustedInput, combo_library: &ComboLibrary) -> CharacterAction {
+
+        if let Some(character_action) = self.input_recognizer.process_input(frame_input, combo_library) {
+            return character_action;
+        }
+
+        //Throw has to come first, as it is made up of the light buttons
+        if frame_input.throw {
+            return CharacterAction::Throw;
+        }
+        else if frame_input.light_attack {
+            return CharacterAction::LightAttack;
+        }
+        else if frame_input.medium_attack {
+            return CharacterAction::MediumAttack;
+        }
+        else if frame_input.heavy_attack {
+            return CharacterAction::HeavyAttack;
+        }
+        else if frame_input.light_kick {
+            return CharacterAction::LightKick;
+        }
+        else if frame_input.medium_kick {
+            return CharacterAction::MediumKick;
+        }
+        else if frame_input.heavy_kick {
+            return CharacterAction::HeavyKick;
+        }
+        else if frame_input.forward_down {
+            return CharacterAction::MoveForward;
+        }
+        else if frame_input.backward_down {
+            return CharacterAction::MoveBackward;
+        }
+        else if frame_input.down_key_down {
+            return CharacterAction::Crouch;
+        }
+        else if frame_input.jump {
+            //TODO: make jump work again
+            return CharacterAction::Jump;
+        }
+        
+
+        return CharacterAction::None;
+    }
+
+
+    //Returns if the character is in the subset of states that are "damageable"
+    //Characters in hit recovery can still be hit, that is what makes a combo, the hit registry keeps one attack from landing over and over
+    //At some point we should remove this, and simply  have frames marked as "invulnerable"
+    //TODO: do above comment
+    pub fn is_in_damageable_state(&self) -> bool {
+        match self.character_state {
+            CharacterState::Thrown 
+                | CharacterState::KnockedDown 
+                | CharacterState::WakeUp => {
+                    return false;
+            },
+            CharacterState::AirHit => {
+                return self.juggle_count < JUGGLE_LIMIT;
+            },
+            _ => {
+                return true;
+            }
+        }
+    }
+
+    pub fn is_airborne(&self) -> bool {
+        match self.character_state {
+            CharacterState::Jump 
+                | CharacterState::ForwardJump 
+                | CharacterState::AirHit => {
+                    return true;
+            },
+            _ => {
+                return self.character_position.y > Fixed::ZERO;
+            }
+        }
+    }
+
+    pub fn is_in_hitstun(&self) -> bool {
+        return (self.character_state == CharacterState::LightHitRecovery && self.recovery_frames > 0) || self.character_state == CharacterState::AirHit;
+    }
+
+    //Characters in hitstun or blockstun, in the air, or already in a throw can not be thrown
+    pub fn is_throwable(&self) -> bool {
+        if self.recovery_frames > 0 {
+            return false;
+        }
+        match self.character_state {
+            CharacterState::Jump 
+                | CharacterState::ForwardJump 
+                | CharacterState::Throwing 
+                | CharacterState::Thrown 
+                | CharacterState::LightHitRecovery 
+                | CharacterState::Parried 
+                | CharacterState::AirHit 
+                | CharacterState::KnockedDown 
+                | CharacterState::WakeUp => {
+                    return false;
+            },
+            _ => {
+                return true;
+            }
+        }
+    }
+
+    //If the character is in the middle of a move that could hit the other character
+    pub fn is_attacking(&self) -> bool {
+        match self.character_state {
+            CharacterState::LightAttack 
+                | CharacterState::MediumAttack 
+                | CharacterState::HeavyAttack 
+                | CharacterState::LightKick 
+                | CharacterState::MediumKick 
+                | CharacterState::HeavyKick 
+                | CharacterState::Special1 => {
+                    return true;
+            },
+            _ => {
+                return false;
+            }
+        }
+    }
+
+    //Blocks can only be started from neutral, or held from a block already in progress
+    pub fn can_block(&self) -> bool {
+        match self.character_state {
+            CharacterState::Idle 
+                | CharacterState::BackwardRun 
+                | CharacterState::Crouching 
+                | CharacterState::Blocking => {
+                    return true;
+            },
+            _ => {
+                return false;
+            }
+        }
+    }
+
+    //A function used to get the information need to lookup a collision box
+    pub fn get_collision_box_lookup_info(&self) -> (AnimationState, u32) {
+        let current_frame = self.current_animation.current_frame;
+        return (self.animation_state, current_frame);
+    }
+
+    // The "Walk box" is what AABB used to move the character
+    // It is not part of the collision system used for combat
+    pub fn get_walk_box(&self)  -> AABB2D {
+        /*
+        //If the character is crouched the walk box is shorter
+        if self.is_crouched {
+            return AABB2D::new(self.character_position.x + 131.0, self.character_position.y + 30.0, 
+                self.character_position.x + 131.0 + 33.0, self.character_position.y + 30.0 + 103.0);
+        }
+        */
+        //These numbers are ones that I grabbed off of my old first passs on hit boxes
+        //They need to be data driven at some point
+        //TODO: REMOVE THE MAGIC NUMBERS
+        return AABB2D::from_ints(WALK_BOX_OFFSET_X, WALK_BOX_OFFSET_Y, WALK_BOX_OFFSET_X + 33, 67).translate(self.character_position);
+    }
+
+    //What the move the character is currently doing will do if it connects
+    pub fn get_current_hit_properties(&self, game_config: &GameplayConfig) -> HitProperties {
+        return game_config.get_hit_properties(self.animation_state);
+    }
+
+    //If the current move is in a cancel window that lets it go straight into new_state
+    pub fn can_cancel_into(&self, new_state: CharacterState, game_config: &GameplayConfig) -> bool {
+        let current_frame = self.current_animation.current_frame;
+        return game_config.get_cancel_windows(self.animation_state).iter().any(|x| x.allows(current_frame, self.move_contact, new_state));
+    }
+
+    #[inline(always)]
+    pub fn can_attack(&self) -> bool {
+        let is_in_idle_state = self.character_state == CharacterState::Idle 
+            || self.character_state == CharacterState::ForwardRun 
+            || self.character_state == CharacterState::BackwardRun
+            || self.character_state == CharacterState::ForwardDash;
+        return is_in_idle_state;
+    }
+}
+
+//TODO: make this data driven, this is tedious and error prone
+impl Default for Character {
+    fn default() -> Self {
+        return Character::new(ScreenSide::Left);
+    }
+}
+
+impl StateHash for Character {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u32(self.animation_state as u32);
+        hasher.write_u32(self.character_state as u32);
+        self.current_animation.hash_state(hasher);
+        self.character_position.hash_state(hasher);
+        self.character_velocity.hash_state(hasher);
+        hasher.write_u32(self.screen_side as u32);
+        hasher.write_u32(self.health);
+        hasher.write_u32(self.stun);
+        hasher.write_bool(self.is_crouched);
+        hasher.write_bool(self.is_jumping);
+        self.input_recognizer.hash_state(hasher);
+        hasher.write_bool(self.done);
+        self.move_starting_screen_side.hash_state(hasher);
+        hasher.write_u32(self.stun_bar);
+        hasher.write_u32(self.recovery_frames);
+        self.pushback.hash_state(hasher);
+        hasher.write_u32(self.throw_tech_frames);
+        self.hit_registry.hash_state(hasher);
+        hasher.write_u32(self.juggle_count);
+        hasher.write_bool(self.rise_chosen);
+        hasher.write_u32(self.move_contact as u32);
+    }
+}
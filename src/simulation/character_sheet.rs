@@ -0,0 +1,265 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::*;
+
+//A displacement is the amount alone the x and y axis a animation wants to move the character
+//it may not, if for say moving along the x axis the character bumps into the edge of the map
+//or the other player
+#[derive(Serialize, Deserialize,Clone, Debug)]
+pub struct  Displacement {
+    #[serde(with = "fixed_as_float")]
+    pub x: Fixed,
+    #[serde(with = "fixed_as_float")]
+    pub y: Fixed
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub enum BoxType {
+    Hit,
+    Hurt,
+    Throw
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct AABB {
+    box_type: BoxType,
+    origin: (i32, i32),
+    size: (u32, u32)
+}
+
+impl AABB {
+    pub fn new(box_type: BoxType, origin: (i32, i32), size: (u32, u32)) -> AABB {
+        AABB {
+            box_type,
+            origin,
+            size
+        }
+    }
+
+    pub fn into_collision_box(&self) -> CollisionBox {
+        
+        let collision_box = match self.box_type {
+            BoxType::Hit => {
+                CollisionBoxType::Hit
+            },
+            BoxType::Hurt => {
+                CollisionBoxType::Hurt
+            },
+            BoxType::Throw => {
+                CollisionBoxType::Throw
+            }
+        };
+        
+
+        CollisionBox::new(collision_box, AABB2D::from_ints(self.origin.0,
+                                                             self.origin.1,
+                                                             self.size.0 as i32 + self.origin.0,
+                                                             self.size.1 as i32 + self.origin.1))
+    }
+}
+
+//Where an attack has to be guarded, a block with the wrong guard does not count
+//High and Mid attacks can be blocked standing or crouching
+//Low attacks have to be blocked crouching, Overheads have to be blocked standing
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+pub enum GuardType {
+    High,
+    Mid,
+    Low,
+    Overhead
+}
+
+impl GuardType {
+    pub fn can_be_blocked(&self, is_crouched: bool) -> bool {
+        match self {
+            GuardType::High | GuardType::Mid => {
+                true
+            },
+            GuardType::Low => {
+                is_crouched
+            },
+            GuardType::Overhead => {
+                !is_crouched
+            }
+        }
+    }
+}
+
+impl Default for GuardType {
+    fn default() -> GuardType {
+        GuardType::Mid
+    }
+}
+
+//How the defender reacts to getting hit, anyone hit in the air always gets an air hit reaction
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HitReaction {
+    Stagger, //Normal hit recovery on the ground
+    Knockdown, //Popped off their feet and put on the ground
+    Launch //Sent into the air by launch_velocity, where they can be juggled
+}
+
+impl Default for HitReaction {
+    fn default() -> HitReaction {
+        HitReaction::Stagger
+    }
+}
+
+//What happens when a move connects, all of the stun values are in game frames
+//Moves without any hit properties in the sheet fall back to the defaults
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct HitProperties {
+    pub damage: u32, //Health removed on hit
+    pub chip_damage: u32, //Health removed on block
+    pub hitstun: u32, //How long the defender is stuck in hit recovery
+    pub blockstun: u32, //How long the defender is stuck blocking
+    pub hit_stop: u32, //How long the whole round freezes when the move connects
+    #[serde(with = "fixed_as_float")]
+    pub pushback: Fixed, //How far the defender slides each frame of hitstun or blockstun
+    pub stun: u32, //How much gets added to the defenders stun meter, only when the move hits
+    #[serde(default)]
+    pub guard: GuardType, //How the defender has to block it
+    #[serde(default = "default_hits")]
+    pub hits: u32, //How many times one activation of the move can hit the same target
+    #[serde(default)]
+    pub hit_interval: u32, //For multi hit moves, how many frames have to pass between hits
+    #[serde(default)]
+    pub reaction: HitReaction,
+    #[serde(default, with = "fixed_as_float")]
+    pub launch_velocity: Fixed //How fast a Launch sends the defender upwards
+}
+
+fn default_hits() -> u32 {
+    1
+}
+
+impl Default for HitProperties {
+    fn default() -> HitProperties {
+        HitProperties {
+            damage: 0,
+            chip_damage: 0,
+            hitstun: 12,
+            blockstun: 8,
+            hit_stop: 3,
+            pushback: Fixed::from_int(5),
+            stun: 10,
+            guard: GuardType::Mid,
+            hits: 1,
+            hit_interval: 0,
+            reaction: HitReaction::Stagger,
+            launch_velocity: Fixed::ZERO
+        }
+    }
+}
+
+//What a move has to have done before a cancel window opens
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+pub enum CancelCondition {
+    Always, //Whiff cancels
+    OnHit,
+    OnHitOrBlock
+}
+
+impl Default for CancelCondition {
+    fn default() -> CancelCondition {
+        CancelCondition::OnHitOrBlock
+    }
+}
+
+//A range of animation frames, counted from 0 like collision_data, where the move can be cut
+//short and go straight into one of the listed moves
+//Chains between normals are just cancel windows into other normals
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelWindow {
+    pub start_frame: u32,
+    pub end_frame: u32, //Inclusive
+    #[serde(default)]
+    pub condition: CancelCondition,
+    pub into: Vec<CharacterState>
+}
+
+impl CancelWindow {
+    pub fn allows(&self, frame: u32, contact: MoveContact, into: CharacterState) -> bool {
+        if frame < self.start_frame || frame > self.end_frame {
+            return false;
+        }
+        let condition_met = match self.condition {
+            CancelCondition::Always => true,
+            CancelCondition::OnHit => contact == MoveContact::Hit,
+            CancelCondition::OnHitOrBlock => contact != MoveContact::None
+        };
+        return condition_met && self.into.contains(&into);
+    }
+}
+
+//Animation data is the information for a single Animation like "Idle" or "HeavyAttack"
+//it includes the location of the sprite sheet, the Hit and HurtBoxs
+//the length of time in in game frames each frame of animation will take
+//how much the character will try to move in the x and y direction those frames
+//and, for attacks, what the move does when it connects and what it can be cancelled into
+#[derive(Serialize, Deserialize,Clone, Debug)]
+pub struct AnimationData {
+    pub image_file_location: String,
+    pub collision_data: Vec<Vec<AABB>>,
+    pub frame_lengths: Vec<u8>,
+    pub displacements: Vec<Displacement>,
+    #[serde(default)]
+    pub hit_properties: Option<HitProperties>,
+    #[serde(default)]
+    pub cancel_windows: Vec<CancelWindow>
+}
+
+//All the information needed to load a character into game
+//there name
+//how fast they are moving
+//and the data for each of their animationscar
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CharacterSheet {
+    pub name: String,
+    pub movespeed: f32,
+    //Percent of a moves damage that is kept for each hit of a combo, the last entry is used for any hits past the end
+    #[serde(default = "default_combo_scaling")]
+    pub combo_scaling: Vec<u32>,
+    //The motion inputs for special moves, dashes and the like, written like "236+LP"
+    #[serde(default)]
+    pub move_inputs: Vec<MoveInput>,
+    pub animations: HashMap<String, AnimationData>
+}
+
+impl CharacterSheet {
+    //Two sheets with the same hash play out exactly the same, used to check a replay is watched with the sheet it was recorded on
+    //Animations go in by name so the HashMap's iteration order does not change the result
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        let header = (&self.name, self.movespeed, &self.combo_scaling, &self.move_inputs);
+        hasher.write_bytes(&bincode::serialize(&header).unwrap());
+
+        let mut names: Vec<&String> = self.animations.keys().collect();
+        names.sort();
+        for name in names {
+            hasher.write_bytes(&bincode::serialize(&(name, &self.animations[name])).unwrap());
+        }
+        return hasher.finish();
+    }
+}
+
+fn default_combo_scaling() -> Vec<u32> {
+    vec![100]
+}
+
+impl StateHash for HitProperties {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u32(self.damage);
+        hasher.write_u32(self.chip_damage);
+        hasher.write_u32(self.hitstun);
+        hasher.write_u32(self.blockstun);
+        hasher.write_u32(self.hit_stop);
+        self.pushback.hash_state(hasher);
+        hasher.write_u32(self.stun);
+        hasher.write_u32(self.guard as u32);
+        hasher.write_u32(self.hits);
+        hasher.write_u32(self.hit_interval);
+        hasher.write_u32(self.reaction as u32);
+        self.launch_velocity.hash_state(hasher);
+    }
+}
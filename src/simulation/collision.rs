@@ -1,5 +1,4 @@
 use asefile::AsepriteFile;
-use storm::math::AABB2D;
 
 use hashbrown::HashMap;
 use crate::*;
@@ -7,26 +6,11 @@ use crate::*;
 use super::{FRAME_WIDTH, AnimationState};
 pub static FIREBALL_COLLISION: &[u8] = include_bytes!("../../resources/fireball_main.ase");
 
-pub trait Reflect {
-    fn reflect(&self, x_axis: usize) -> AABB2D;
-}
-
-impl Reflect for AABB2D {
-    fn reflect(&self, x_axis: usize) -> AABB2D {
-        let min_dif = self.min.x - x_axis as f32;
-        let max_dif = self.max.x - x_axis as f32;
-        AABB2D::new(
-            self.min.x - (min_dif + max_dif),
-            self.min.y,
-            self.max.x - (min_dif + max_dif),
-            self.max.y)
-    }
-}
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum CollisionBoxType {
     Hurt,
-    Hit
+    Hit,
+    Throw
 }
 #[derive(Debug)]
 pub struct CollisionBox {
@@ -52,11 +36,11 @@ pub struct CollisionReport {
     pub collider_type: CollisionBoxType,
     pub collide_type: CollisionBoxType,
     pub collider_character: CharacterNumber,
-    pub where_x: f32
+    pub where_x: Fixed
 }
 
 impl CollisionReport {
-    pub fn new(collider_type: CollisionBoxType, collide_type: CollisionBoxType, collider_character: CharacterNumber, where_x: f32) -> CollisionReport {
+    pub fn new(collider_type: CollisionBoxType, collide_type: CollisionBoxType, collider_character: CharacterNumber, where_x: Fixed) -> CollisionReport {
         CollisionReport {
             collider_type,
             collide_type,
@@ -115,11 +99,11 @@ impl CollisionInfo {
             let offset = index * FRAME_WIDTH;
             //Because we are doing it this way we also need to normalize the x coordinate of the AABB
             //Y is fine
-            let fixed_x_min = b.0 as f32 - offset as f32;
-            let aabb = AABB2D::new(fixed_x_min, 
-                                          b.1 as f32 , 
-                                          c.0 as f32 + fixed_x_min, 
-                                          c.1 as f32 + b.1 as f32);
+            let fixed_x_min = b.0 - offset as i32;
+            let aabb = AABB2D::from_ints(fixed_x_min,
+                                          b.1,
+                                          c.0 as i32 + fixed_x_min,
+                                          c.1 as i32 + b.1);
             if collision_info.frame_collision.contains_key(&index) == false {
                 collision_info.frame_collision.insert(index, vec![]);
             }
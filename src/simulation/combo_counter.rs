@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use super::{StateHash, StateHasher};
+
+//How long the combo count stays up after the combo has been dropped, in game frames
+pub const COMBO_DISPLAY_FRAMES: u32 = 60;
+
+//Tracks the hits one character lands on the other while they stay in hitstun
+//It lives in the Round so that it is rolled back along with everything else
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug)]
+pub struct ComboCounter {
+    pub hits: u32,
+    pub damage: u32, //Total damage of the combo, after scaling
+    pub in_progress: bool,
+    pub display_frames: u32 //Counts down once the combo is dropped, the HUD hides the count when it hits zero
+}
+
+impl ComboCounter {
+    pub fn new() -> ComboCounter {
+        ComboCounter {
+            hits: 0,
+            damage: 0,
+            in_progress: false,
+            display_frames: 0
+        }
+    }
+
+    //The first hit of a new combo
+    pub fn start(&mut self) {
+        self.hits = 0;
+        self.damage = 0;
+        self.in_progress = true;
+    }
+
+    pub fn add_hit(&mut self, damage: u32) {
+        self.hits += 1;
+        self.damage += damage;
+        self.display_frames = COMBO_DISPLAY_FRAMES;
+    }
+
+    //The defender got out of hitstun
+    pub fn drop_combo(&mut self) {
+        self.in_progress = false;
+    }
+
+    pub fn tick(&mut self) {
+        if self.in_progress == false && self.display_frames > 0 {
+            self.display_frames -= 1;
+        }
+    }
+
+    //A single hit is not a combo
+    pub fn should_display(&self) -> bool {
+        return self.hits > 1 && (self.in_progress || self.display_frames > 0);
+    }
+}
+
+impl StateHash for ComboCounter {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u32(self.hits);
+        hasher.write_u32(self.damage);
+        hasher.write_bool(self.in_progress);
+        hasher.write_u32(self.display_frames);
+    }
+}
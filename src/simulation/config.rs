@@ -0,0 +1,159 @@
+use hashbrown::HashMap;
+use super::*;
+
+//Everything the simulation needs to run a round, none of it is GPU backed
+//so it can be built and used without a window
+pub struct GameplayConfig {
+    pub collision_library: CollisionLibrary,
+    pub combo_library: ComboLibrary,
+    pub animation_for_character_state_library: HashMap<CharacterState, AnimationStateForCharacterState>,
+    pub animation_configs: HashMap<AnimationState, AnimationConfig>,
+    pub hit_properties: HashMap<AnimationState, HitProperties>,
+    pub cancel_windows: HashMap<AnimationState, Vec<CancelWindow>>,
+    pub character_sheet: CharacterSheet,
+    pub config_hash: u64 //The content hash of the character sheet, replays are tied to it
+}
+
+impl GameplayConfig {
+    pub fn new(collision_library: CollisionLibrary,
+               combo_library: ComboLibrary,
+               animation_for_character_state_library: HashMap<CharacterState, AnimationStateForCharacterState>,
+               animation_configs: HashMap<AnimationState, AnimationConfig>,
+               character_sheet: CharacterSheet) -> GameplayConfig {
+        let mut hit_properties = HashMap::new();
+        let mut cancel_windows = HashMap::new();
+        for (name, animation_data) in character_sheet.animations.iter() {
+            if let Some(properties) = animation_data.hit_properties {
+                hit_properties.insert(AnimationState::from_string(name), properties);
+            }
+            if animation_data.cancel_windows.len() > 0 {
+                cancel_windows.insert(AnimationState::from_string(name), animation_data.cancel_windows.clone());
+            }
+        }
+
+        let config_hash = character_sheet.content_hash();
+
+        GameplayConfig {
+            collision_library,
+            combo_library,
+            animation_for_character_state_library,
+            animation_configs,
+            hit_properties,
+            cancel_windows,
+            character_sheet,
+            config_hash
+        }
+    }
+
+    pub fn get_animation_config(&self, animation_state: AnimationState) -> &AnimationConfig {
+        return self.animation_configs.get(&animation_state).unwrap();
+    }
+
+    //Looks up what a move does when it connects, moves without any data in the sheet use the defaults
+    pub fn get_hit_properties(&self, animation_state: AnimationState) -> HitProperties {
+        match self.hit_properties.get(&animation_state) {
+            Some(hit_properties) => *hit_properties,
+            None => HitProperties::default()
+        }
+    }
+
+    //How much damage the next hit of a combo does, hits_landed is how many hits the combo already has
+    pub fn scale_combo_damage(&self, damage: u32, hits_landed: u32) -> u32 {
+        let combo_scaling = &self.character_sheet.combo_scaling;
+        let percent = match combo_scaling.get(hits_landed as usize) {
+            Some(percent) => *percent,
+            None => *combo_scaling.last().unwrap_or(&100)
+        };
+        return damage * percent / 100;
+    }
+
+    pub fn get_cancel_windows(&self, animation_state: AnimationState) -> &[CancelWindow] {
+        match self.cancel_windows.get(&animation_state) {
+            Some(cancel_windows) => cancel_windows,
+            None => &[]
+        }
+    }
+
+    //Builds the full config from a character sheet, this is all a headless simulation needs
+    //Fails on sheet data the simulation can not use, like a move whose notation does not parse
+    pub fn new_from_sheet(character_sheet: CharacterSheet) -> Result<GameplayConfig, String> {
+        let mut animation_for_character_state_library = HashMap::new();
+        animation_for_character_state_library.insert(CharacterState::Idle, AnimationStateForCharacterState::new(AnimationState::Crouched, AnimationState::Idle, AnimationState::Idle));
+        animation_for_character_state_library.insert(CharacterState::ForwardRun, AnimationStateForCharacterState::new(AnimationState::ForwardRun, AnimationState::ForwardRun, AnimationState::ForwardRun));
+        animation_for_character_state_library.insert(CharacterState::BackwardRun, AnimationStateForCharacterState::new(AnimationState::BackwardRun, AnimationState::BackwardRun, AnimationState::BackwardRun));
+        animation_for_character_state_library.insert(CharacterState::LightHitRecovery, AnimationStateForCharacterState::new(AnimationState::LightHitRecovery, AnimationState::LightHitRecovery, AnimationState::LightHitRecovery));
+        animation_for_character_state_library.insert(CharacterState::Blocking, AnimationStateForCharacterState::new(AnimationState::Blocking, AnimationState::Blocking, AnimationState::Blocking));
+        animation_for_character_state_library.insert(CharacterState::Crouching, AnimationStateForCharacterState::new(AnimationState::Crouching, AnimationState::Crouching, AnimationState::Crouching));
+        animation_for_character_state_library.insert(CharacterState::LightAttack, AnimationStateForCharacterState::new(AnimationState::LightCrouchAttack, AnimationState::LightAttack, AnimationState::LightJumpingKick));
+        animation_for_character_state_library.insert(CharacterState::MediumAttack, AnimationStateForCharacterState::new(AnimationState::LightCrouchAttack, AnimationState::MediumAttack, AnimationState::MediumAttack));
+        animation_for_character_state_library.insert(CharacterState::HeavyAttack, AnimationStateForCharacterState::new(AnimationState::HeavyCrouchingAttack, AnimationState::HeavyAttack, AnimationState::HeavyAttack));
+        animation_for_character_state_library.insert(CharacterState::LightKick, AnimationStateForCharacterState::new(AnimationState::LightCrouchKick, AnimationState::LightKick, AnimationState::LightKick));
+        animation_for_character_state_library.insert(CharacterState::MediumKick, AnimationStateForCharacterState::new(AnimationState::MediumCrouchKick, AnimationState::MediumKick, AnimationState::MediumKick));
+        animation_for_character_state_library.insert(CharacterState::HeavyKick, AnimationStateForCharacterState::new(AnimationState::HeavyCrouchKick, AnimationState::HeavyKick, AnimationState::HeavyKick));
+        animation_for_character_state_library.insert(CharacterState::ForwardDash, AnimationStateForCharacterState::new(AnimationState::ForwardDash, AnimationState::ForwardDash, AnimationState::ForwardDash));
+        animation_for_character_state_library.insert(CharacterState::BackwardDash, AnimationStateForCharacterState::new(AnimationState::BackwardDash, AnimationState::BackwardDash, AnimationState::BackwardDash));
+        animation_for_character_state_library.insert(CharacterState::Special1, AnimationStateForCharacterState::new(AnimationState::Special1, AnimationState::Special1, AnimationState::Special1));
+        animation_for_character_state_library.insert(CharacterState::Won, AnimationStateForCharacterState::new(AnimationState::Won, AnimationState::Won, AnimationState::Won));
+        animation_for_character_state_library.insert(CharacterState::Lost, AnimationStateForCharacterState::new(AnimationState::Lost, AnimationState::Lost, AnimationState::Lost));
+        animation_for_character_state_library.insert(CharacterState::Jump, AnimationStateForCharacterState::new(AnimationState::Jump, AnimationState::Jump, AnimationState::Jump));
+        animation_for_character_state_library.insert(CharacterState::Parry, AnimationStateForCharacterState::new(AnimationState::Parry, AnimationState::Parry, AnimationState::Parry));
+        animation_for_character_state_library.insert(CharacterState::Parried, AnimationStateForCharacterState::new(AnimationState::LightHitRecovery, AnimationState::LightHitRecovery, AnimationState::LightHitRecovery));
+        animation_for_character_state_library.insert(CharacterState::ForwardJump, AnimationStateForCharacterState::new(AnimationState::ForwardJump, AnimationState::ForwardJump, AnimationState::ForwardJump));
+        animation_for_character_state_library.insert(CharacterState::Dizzie, AnimationStateForCharacterState::new(AnimationState::Dizzie, AnimationState::Dizzie, AnimationState::Dizzie));
+        animation_for_character_state_library.insert(CharacterState::Throwing, AnimationStateForCharacterState::new(AnimationState::Throw, AnimationState::Throw, AnimationState::Throw));
+        animation_for_character_state_library.insert(CharacterState::Thrown, AnimationStateForCharacterState::new(AnimationState::Thrown, AnimationState::Thrown, AnimationState::Thrown));
+        animation_for_character_state_library.insert(CharacterState::AirHit, AnimationStateForCharacterState::new(AnimationState::AirHit, AnimationState::AirHit, AnimationState::AirHit));
+        animation_for_character_state_library.insert(CharacterState::KnockedDown, AnimationStateForCharacterState::new(AnimationState::KnockedDown, AnimationState::KnockedDown, AnimationState::KnockedDown));
+        animation_for_character_state_library.insert(CharacterState::WakeUp, AnimationStateForCharacterState::new(AnimationState::WakeUp, AnimationState::WakeUp, AnimationState::WakeUp));
+
+        let animation_state = vec![
+            AnimationState::Idle,
+            AnimationState::ForwardRun,
+            AnimationState::BackwardRun,
+            AnimationState::LightAttack,
+            AnimationState::MediumAttack,
+            AnimationState::HeavyAttack,
+            AnimationState::LightHitRecovery,
+            AnimationState::Crouched,
+            AnimationState::Crouching,
+            AnimationState::Blocking,
+            AnimationState::LightCrouchAttack,
+            AnimationState::HeavyCrouchingAttack,
+            AnimationState::LightKick,
+            AnimationState::MediumKick,
+            AnimationState::HeavyKick,
+            AnimationState::ForwardDash,
+            AnimationState::BackwardDash,
+            AnimationState::Special1,
+            AnimationState::Won,
+            AnimationState::Lost,
+            AnimationState::Jump,
+            AnimationState::Parry,
+            AnimationState::ForwardJump,
+            AnimationState::LightCrouchKick,
+            AnimationState::MediumCrouchKick,
+            AnimationState::HeavyCrouchKick,
+            AnimationState::Throw,
+            AnimationState::Thrown,
+            AnimationState::AirHit,
+            AnimationState::KnockedDown,
+            AnimationState::WakeUp
+        ];
+
+        let mut animation_configs = HashMap::new();
+        for state in animation_state {
+            let animation = character_sheet.animations.get(&state.to_string()).ok_or(format!("the sheet has no {} animation", state.to_string()))?;
+            animation_configs.insert(state, AnimationConfig::new(animation.frame_lengths.clone()));
+        }
+
+        let combo_library;
+        if character_sheet.move_inputs.len() > 0 {
+            combo_library = ComboLibrary::new(&character_sheet.move_inputs)?;
+        }
+        else {
+            combo_library = ComboLibrary::default();
+        }
+
+        Ok(GameplayConfig::new(CollisionLibrary::new_from_sheet(&character_sheet), combo_library, animation_for_character_state_library, animation_configs, character_sheet))
+    }
+}
@@ -2,7 +2,7 @@ use crate::*;
 use serde::{Deserialize, Serialize};
 
 
-#[derive(Serialize, Deserialize,  Clone)]
+#[derive(Serialize, Deserialize, Copy, Clone)]
 pub enum EffectKind {
     Low,
     Medium,
@@ -10,21 +10,21 @@ pub enum EffectKind {
 }
 
 // A Tracker of an effect in the world, how long it will last, and what level it is
-#[derive(Serialize, Deserialize,  Clone)]
+#[derive(Serialize, Deserialize, Copy, Clone)]
 pub struct Effect {
     pub sprite_timer: SpriteTimer,
     pub number_of_frames: usize,
     pub current_frame: usize,
     pub is_use: bool,
     pub effect_kind: EffectKind,
-    pub position_x: f32,
-    pub position_y: f32,
+    pub position_x: Fixed,
+    pub position_y: Fixed,
     pub screen_side: ScreenSide
 }
 
 
 impl Effect {
-    pub fn new(frame_duration: usize, number_of_frames: usize, effect_kind: EffectKind, position_x: f32, position_y: f32, screen_side: ScreenSide) -> Effect {
+    pub fn new(frame_duration: usize, number_of_frames: usize, effect_kind: EffectKind, position_x: Fixed, position_y: Fixed, screen_side: ScreenSide) -> Effect {
         Effect {
             sprite_timer: SpriteTimer::new(frame_duration as u32),
             number_of_frames,
@@ -48,3 +48,21 @@ impl Effect {
     }
 }
 
+impl Default for Effect {
+    fn default() -> Effect {
+        return Effect::new(1, 1, EffectKind::Low, Fixed::ZERO, Fixed::ZERO, ScreenSide::Right);
+    }
+}
+
+impl StateHash for Effect {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.sprite_timer.hash_state(hasher);
+        hasher.write_usize(self.number_of_frames);
+        hasher.write_usize(self.current_frame);
+        hasher.write_bool(self.is_use);
+        hasher.write_u32(self.effect_kind as u32);
+        self.position_x.hash_state(hasher);
+        self.position_y.hash_state(hasher);
+        hasher.write_u32(self.screen_side as u32);
+    }
+}
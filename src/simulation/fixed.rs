@@ -0,0 +1,234 @@
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use serde::{Deserialize, Serialize};
+
+pub const FIXED_FRACTIONAL_BITS: u32 = 16;
+const FIXED_ONE: i32 = 1 << FIXED_FRACTIONAL_BITS;
+
+//A 16.16 fixed point number, every value that lives inside the rolled back Round uses this
+//instead of f32 so that the native client and the wasm build always agree bit for bit
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Copy, Clone, Default, Debug)]
+pub struct Fixed(i32);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(FIXED_ONE);
+
+    pub const fn from_int(value: i32) -> Fixed {
+        Fixed(value << FIXED_FRACTIONAL_BITS)
+    }
+
+    //numerator / denominator, used to write constants like 1.2 as from_ratio(6, 5)
+    pub const fn from_ratio(numerator: i32, denominator: i32) -> Fixed {
+        Fixed(((numerator as i64) * (FIXED_ONE as i64) / (denominator as i64)) as i32)
+    }
+
+    pub const fn from_bits(bits: i32) -> Fixed {
+        Fixed(bits)
+    }
+
+    pub const fn to_bits(self) -> i32 {
+        self.0
+    }
+
+    //Only meant for loading data, like the character sheet, never for math inside the simulation
+    pub fn from_f32(value: f32) -> Fixed {
+        Fixed((value * FIXED_ONE as f32).round() as i32)
+    }
+
+    //Only meant for rendering
+    pub fn to_f32(self) -> f32 {
+        self.0 as f32 / FIXED_ONE as f32
+    }
+
+    //Rounds towards negative infinity
+    pub fn to_int(self) -> i32 {
+        self.0 >> FIXED_FRACTIONAL_BITS
+    }
+
+    pub fn abs(self) -> Fixed {
+        Fixed(self.0.abs())
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, other: Fixed) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0 - other.0)
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, other: Fixed) {
+        self.0 -= other.0;
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, other: Fixed) -> Fixed {
+        Fixed(((self.0 as i64 * other.0 as i64) >> FIXED_FRACTIONAL_BITS) as i32)
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, other: Fixed) {
+        *self = *self * other;
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+    fn div(self, other: Fixed) -> Fixed {
+        Fixed((((self.0 as i64) << FIXED_FRACTIONAL_BITS) / other.0 as i64) as i32)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Fixed {
+        Fixed(-self.0)
+    }
+}
+
+//The fixed point version of a cgmath Vector2
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone, Default, Debug)]
+pub struct FixedVector2 {
+    pub x: Fixed,
+    pub y: Fixed
+}
+
+impl FixedVector2 {
+    pub const fn new(x: Fixed, y: Fixed) -> FixedVector2 {
+        FixedVector2 {
+            x,
+            y
+        }
+    }
+
+    pub fn zero() -> FixedVector2 {
+        FixedVector2::new(Fixed::ZERO, Fixed::ZERO)
+    }
+
+    pub fn to_f32(self) -> cgmath::Vector2<f32> {
+        cgmath::Vector2::new(self.x.to_f32(), self.y.to_f32())
+    }
+}
+
+impl Add for FixedVector2 {
+    type Output = FixedVector2;
+    fn add(self, other: FixedVector2) -> FixedVector2 {
+        FixedVector2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for FixedVector2 {
+    fn add_assign(&mut self, other: FixedVector2) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl Sub for FixedVector2 {
+    type Output = FixedVector2;
+    fn sub(self, other: FixedVector2) -> FixedVector2 {
+        FixedVector2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<Fixed> for FixedVector2 {
+    type Output = FixedVector2;
+    fn mul(self, other: Fixed) -> FixedVector2 {
+        FixedVector2::new(self.x * other, self.y * other)
+    }
+}
+
+//Lets data files keep writing plain floats, ie "x": 1.5, while we store Fixed
+//use with #[serde(with = "fixed_as_float")]
+pub mod fixed_as_float {
+    use super::Fixed;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Fixed, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(value.to_f32())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fixed, D::Error> {
+        let value = f32::deserialize(deserializer)?;
+        Ok(Fixed::from_f32(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_survive_the_round_trip() {
+        for value in [-1000, -1, 0, 1, 7, 1000].iter() {
+            assert_eq!(Fixed::from_int(*value).to_int(), *value);
+        }
+        assert_eq!(Fixed::from_int(1), Fixed::ONE);
+        assert_eq!(Fixed::from_int(0), Fixed::ZERO);
+    }
+
+    #[test]
+    fn to_int_rounds_towards_negative_infinity() {
+        assert_eq!(Fixed::from_ratio(3, 2).to_int(), 1);
+        assert_eq!(Fixed::from_ratio(-3, 2).to_int(), -2);
+        assert_eq!(Fixed::from_ratio(-1, 1000).to_int(), -1);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let one_and_a_half = Fixed::from_ratio(3, 2);
+        assert_eq!(one_and_a_half + one_and_a_half, Fixed::from_int(3));
+        assert_eq!(one_and_a_half - Fixed::from_int(2), -Fixed::from_ratio(1, 2));
+        assert_eq!(one_and_a_half * Fixed::from_int(4), Fixed::from_int(6));
+        assert_eq!(Fixed::from_int(6) / Fixed::from_int(4), one_and_a_half);
+        assert_eq!(Fixed::from_int(-6) / Fixed::from_int(4), -one_and_a_half);
+        assert_eq!((-one_and_a_half).abs(), one_and_a_half);
+
+        let mut value = Fixed::from_int(2);
+        value += Fixed::ONE;
+        value -= Fixed::from_ratio(1, 2);
+        value *= Fixed::from_int(2);
+        assert_eq!(value, Fixed::from_int(5));
+
+        let vector = FixedVector2::new(Fixed::from_int(1), Fixed::from_int(-2)) * one_and_a_half;
+        assert_eq!(vector, FixedVector2::new(one_and_a_half, Fixed::from_int(-3)));
+    }
+
+    #[test]
+    fn floats_from_the_sheet_land_on_the_nearest_fixed() {
+        assert_eq!(Fixed::from_f32(1.5), Fixed::from_ratio(3, 2));
+        assert_eq!(Fixed::from_f32(-0.25), Fixed::from_ratio(-1, 4));
+        assert_eq!(Fixed::from_f32(1.2).to_bits(), 78643);
+        assert_eq!(Fixed::from_ratio(5, 4).to_f32(), 1.25);
+    }
+
+    #[test]
+    fn fixed_as_float_reads_and_writes_plain_floats() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Sheet {
+            #[serde(with = "fixed_as_float")]
+            speed: Fixed
+        }
+
+        let sheet: Sheet = serde_json::from_str("{\"speed\": 2.5}").unwrap();
+        assert_eq!(sheet.speed, Fixed::from_ratio(5, 2));
+        assert_eq!(serde_json::to_string(&sheet).unwrap(), "{\"speed\":2.5}");
+    }
+}
@@ -0,0 +1,141 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+//A list with a capacity fixed at compile time, it lives entirely inline so anything that
+//holds one can be copied as a plain block of memory, which is what makes saving a Round cheap
+//Derefs to a slice of the items in use, so it can be iterated and indexed like a Vec
+#[derive(Copy, Clone)]
+pub struct FixedList<T: Copy + Default, const N: usize> {
+    items: [T; N],
+    len: usize
+}
+
+impl<T: Copy + Default, const N: usize> FixedList<T, N> {
+    pub fn new() -> FixedList<T, N> {
+        FixedList {
+            items: [T::default(); N],
+            len: 0
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        return N;
+    }
+
+    pub fn is_full(&self) -> bool {
+        return self.len == N;
+    }
+
+    //Returns false and drops the item if the list is already full
+    pub fn push(&mut self, item: T) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.items[self.len] = item;
+        self.len += 1;
+        return true;
+    }
+
+    //Pushes the item, throwing away the oldest one if there is no room, used for history buffers
+    pub fn push_evicting_oldest(&mut self, item: T) {
+        if self.is_full() {
+            self.remove(0);
+        }
+        self.push(item);
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let item = self.items[index];
+        self.items.copy_within(index + 1..self.len, index);
+        self.len -= 1;
+        self.items[self.len] = T::default();
+        return item;
+    }
+
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        let mut kept = 0;
+        for index in 0..self.len {
+            if keep(&self.items[index]) {
+                self.items[kept] = self.items[index];
+                kept += 1;
+            }
+        }
+        //Unused slots are kept at their default so two lists with the same items are identical in memory
+        for index in kept..self.len {
+            self.items[index] = T::default();
+        }
+        self.len = kept;
+    }
+
+    pub fn clear(&mut self) {
+        self.retain(|_| false);
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for FixedList<T, N> {
+    fn default() -> FixedList<T, N> {
+        return FixedList::new();
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Deref for FixedList<T, N> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        &self.items[..self.len]
+    }
+}
+
+impl<T: Copy + Default, const N: usize> DerefMut for FixedList<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.items[..self.len]
+    }
+}
+
+impl<T: Copy + Default + fmt::Debug, const N: usize> fmt::Debug for FixedList<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+//Only the items in use are written out, so the unused capacity costs nothing in a serialized Round
+impl<T: Copy + Default + Serialize, const N: usize> Serialize for FixedList<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for item in self.iter() {
+            seq.serialize_element(item)?;
+        }
+        seq.end()
+    }
+}
+
+struct FixedListVisitor<T, const N: usize> {
+    marker: PhantomData<T>
+}
+
+impl<'de, T: Copy + Default + Deserialize<'de>, const N: usize> Visitor<'de> for FixedListVisitor<T, N> {
+    type Value = FixedList<T, N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of at most {} items", N)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<FixedList<T, N>, A::Error> {
+        let mut list = FixedList::new();
+        while let Some(item) = seq.next_element()? {
+            if list.push(item) == false {
+                return Err(A::Error::invalid_length(N + 1, &self));
+            }
+        }
+        Ok(list)
+    }
+}
+
+impl<'de, T: Copy + Default + Deserialize<'de>, const N: usize> Deserialize<'de> for FixedList<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<FixedList<T, N>, D::Error> {
+        deserializer.deserialize_seq(FixedListVisitor { marker: PhantomData })
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use super::*;
+
+//Every activation of an attack, a character move or a projectile, carries one of these
+//It remembers who the attack has already hit so that overlapping boxes, or an active window that
+//lasts more than one frame, only land as many hits as the move says it should
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug)]
+pub struct HitRegistry {
+    pub hits_landed: [u32; 2], //Indexed by the character that got hit
+    pub frames_until_next_hit: [u32; 2]
+}
+
+impl HitRegistry {
+    pub fn new() -> HitRegistry {
+        HitRegistry {
+            hits_landed: [0; 2],
+            frames_until_next_hit: [0; 2]
+        }
+    }
+
+    pub fn can_hit(&self, target: usize, hit_properties: &HitProperties) -> bool {
+        return self.hits_landed[target] < hit_properties.hits && self.frames_until_next_hit[target] == 0;
+    }
+
+    //Always waits at least a frame, so that all of the boxes overlapping on one frame only count once
+    pub fn register_hit(&mut self, target: usize, hit_properties: &HitProperties) {
+        self.hits_landed[target] += 1;
+        self.frames_until_next_hit[target] = hit_properties.hit_interval.max(1);
+    }
+
+    //If the attack has landed all of its hits on someone, projectiles go away once they are spent
+    pub fn is_spent(&self, hit_properties: &HitProperties) -> bool {
+        return self.hits_landed.iter().any(|x| *x >= hit_properties.hits);
+    }
+
+    pub fn tick(&mut self) {
+        for frames in self.frames_until_next_hit.iter_mut() {
+            if *frames > 0 {
+                *frames -= 1;
+            }
+        }
+    }
+
+    //A new activation of the attack, ie the character started a new move
+    pub fn reset(&mut self) {
+        *self = HitRegistry::new();
+    }
+}
+
+impl StateHash for HitRegistry {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        for target in 0..2 {
+            hasher.write_u32(self.hits_landed[target]);
+            hasher.write_u32(self.frames_until_next_hit[target]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit_properties(hits: u32, hit_interval: u32) -> HitProperties {
+        return HitProperties { hits, hit_interval, ..Default::default() };
+    }
+
+    #[test]
+    fn a_single_hit_move_only_hits_each_target_once() {
+        let properties = hit_properties(1, 0);
+        let mut registry = HitRegistry::new();
+        assert!(registry.can_hit(1, &properties));
+        registry.register_hit(1, &properties);
+        assert!(registry.can_hit(1, &properties) == false);
+        assert!(registry.is_spent(&properties));
+
+        //The other character has not been hit yet
+        assert!(registry.can_hit(0, &properties));
+
+        for _ in 0..10 {
+            registry.tick();
+        }
+        assert!(registry.can_hit(1, &properties) == false);
+    }
+
+    #[test]
+    fn boxes_overlapping_on_the_same_frame_only_count_once() {
+        //No interval still waits a frame between hits
+        let properties = hit_properties(3, 0);
+        let mut registry = HitRegistry::new();
+        registry.register_hit(0, &properties);
+        assert!(registry.can_hit(0, &properties) == false);
+        registry.tick();
+        assert!(registry.can_hit(0, &properties));
+    }
+
+    #[test]
+    fn multi_hit_moves_wait_their_interval() {
+        let properties = hit_properties(3, 4);
+        let mut registry = HitRegistry::new();
+        let mut hit_frames = vec![];
+        for frame in 0..20 {
+            if registry.can_hit(1, &properties) {
+                registry.register_hit(1, &properties);
+                hit_frames.push(frame);
+            }
+            registry.tick();
+        }
+        assert_eq!(hit_frames, vec![0, 4, 8]);
+        assert!(registry.is_spent(&properties));
+    }
+
+    #[test]
+    fn reset_starts_a_new_activation() {
+        let properties = hit_properties(1, 0);
+        let mut registry = HitRegistry::new();
+        registry.register_hit(1, &properties);
+        registry.reset();
+        assert!(registry.can_hit(1, &properties));
+        assert!(registry.is_spent(&properties) == false);
+    }
+}
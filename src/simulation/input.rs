@@ -0,0 +1,742 @@
+
+use super::*;
+use serde::{Deserialize, Serialize};
+use bytemuck::{Pod, Zeroable};
+
+//To reduce the size of the input we send between 
+//players we fit it onto a u16
+//We use this to set the position on that u16
+//that each input is
+pub const INPUT_LIGHT_ATTACK: u16 = 1 << 0;
+pub const INPUT_LEFT: u16 = 1 << 1;
+pub const INPUT_RIGHT: u16 = 1 << 2;
+pub const INPUT_DOWN: u16 = 1 << 3;
+pub const INPUT_MEDIUM_ATTACK: u16 = 1 << 4;
+pub const INPUT_HEAVY_ATTACK: u16 = 1 << 5;
+pub const INPUT_LIGHT_KICK: u16 = 1 << 6;
+pub const INPUT_MEDIUM_KICK: u16 = 1 << 7;
+pub const INPUT_HEAVY_KICK: u16 = 1 << 9;
+pub const INPUT_JUMP: u16 = 1 << 8; 
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Pod, Zeroable, Serialize, Deserialize, Debug)]
+pub struct NetInput {
+    pub input: u16
+}
+
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Default, Copy, Clone, Debug)]
+pub struct Input {
+    pub left_key_down:  bool,
+    pub right_key_down: bool,
+    pub down_key_down:  bool,
+    pub light_attack:   bool,
+    pub medium_attack:  bool,
+    pub heavy_attack:   bool,
+    pub light_kick:     bool,
+    pub medium_kick:    bool,
+    pub heavy_kick:     bool,
+    pub jump_down:      bool,
+    pub has_input:      bool
+}
+
+impl Input {
+    pub fn new() -> Input {
+        Input {
+            left_key_down:  false,
+            right_key_down: false,
+            down_key_down:  false,
+            light_attack:   false,
+            medium_attack:  false,
+            heavy_attack:   false,
+            light_kick:     false,
+            medium_kick:    false,
+            heavy_kick:     false,
+            jump_down:      false,
+            has_input:      false
+        }
+    }
+
+    pub fn from_game_input(game_input: NetInput) -> Input {
+        let recombined_input = game_input.input;
+        let has_input = recombined_input > 0;
+        Input {
+            left_key_down:  (recombined_input & INPUT_LEFT) != 0,
+            right_key_down: (recombined_input & INPUT_RIGHT) != 0,
+            down_key_down:  (recombined_input & INPUT_DOWN) != 0,
+            light_attack:   (recombined_input & INPUT_LIGHT_ATTACK) != 0,
+            medium_attack:  (recombined_input & INPUT_MEDIUM_ATTACK) != 0,
+            heavy_attack:   (recombined_input & INPUT_HEAVY_ATTACK) != 0,
+            light_kick:     (recombined_input & INPUT_LIGHT_KICK) != 0,
+            medium_kick:    (recombined_input & INPUT_MEDIUM_KICK) != 0,
+            heavy_kick:     (recombined_input & INPUT_HEAVY_KICK) != 0,
+            jump_down:      (recombined_input & INPUT_JUMP) != 0,
+            has_input
+        }
+    }
+
+    //Packs the buttons back down into what gets sent over the network
+    pub fn to_game_input(&self) -> NetInput {
+        let buttons = [
+            (self.left_key_down, INPUT_LEFT),
+            (self.right_key_down, INPUT_RIGHT),
+            (self.down_key_down, INPUT_DOWN),
+            (self.light_attack, INPUT_LIGHT_ATTACK),
+            (self.medium_attack, INPUT_MEDIUM_ATTACK),
+            (self.heavy_attack, INPUT_HEAVY_ATTACK),
+            (self.light_kick, INPUT_LIGHT_KICK),
+            (self.medium_kick, INPUT_MEDIUM_KICK),
+            (self.heavy_kick, INPUT_HEAVY_KICK),
+            (self.jump_down, INPUT_JUMP)
+        ];
+        let mut input: u16 = 0;
+        for (down, bit) in buttons.iter() {
+            if *down {
+                input |= bit;
+            }
+        }
+        return NetInput { input };
+    }
+}
+
+
+//Similar to the input struct, but will adjust the left and right keys down
+//To Forward and Backward, to allow for side adnostic code
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Default, Copy, Clone, Debug)]
+pub struct ScreenSideAdjustedInput {
+    pub forward_down:   bool,
+    pub backward_down:  bool,
+    pub down_key_down:  bool,
+    pub light_attack:   bool,
+    pub medium_attack:  bool,
+    pub heavy_attack:   bool,
+    pub light_kick:     bool,
+    pub medium_kick:    bool,
+    pub heavy_kick:     bool,
+    pub has_input:      bool,
+    pub jump:           bool,
+    pub throw:          bool //Light punch and light kick pressed together
+}
+
+impl ScreenSideAdjustedInput {
+    pub fn new(input: &Input, screen_side: ScreenSide) -> ScreenSideAdjustedInput {
+        let mut forward = false;
+        if screen_side == ScreenSide::Left && input.right_key_down {
+            forward = true;
+        }
+        if screen_side == ScreenSide::Right && input.left_key_down {
+            forward = true;
+        }
+        let mut backward = false;
+        if screen_side == ScreenSide::Left && input.left_key_down {
+            backward = true;
+        }
+        if screen_side == ScreenSide::Right && input.right_key_down {
+            backward = true;
+        }
+
+        ScreenSideAdjustedInput {
+            forward_down: forward,
+            backward_down: backward,
+            down_key_down:  input.down_key_down,
+            light_attack:   input.light_attack,
+            medium_attack:  input.medium_attack,
+            heavy_attack:   input.heavy_attack,
+            light_kick:     input.light_kick,
+            medium_kick:    input.medium_kick,
+            heavy_kick:     input.heavy_kick,
+            has_input:      input.has_input,
+            jump:           input.jump_down,
+            throw:          input.light_attack && input.light_kick
+        }
+    }
+
+    //Just the buttons that went down on this frame, anything already held on the frame before is left out
+    //With no frame before there is no way to tell a press from a hold, so everything counts as held
+    pub fn pressed_since(&self, previous: Option<ScreenSideAdjustedInput>) -> ScreenSideAdjustedInput {
+        let previous = previous.unwrap_or(*self);
+        ScreenSideAdjustedInput {
+            forward_down:   self.forward_down,
+            backward_down:  self.backward_down,
+            down_key_down:  self.down_key_down,
+            light_attack:   self.light_attack && previous.light_attack == false,
+            medium_attack:  self.medium_attack && previous.medium_attack == false,
+            heavy_attack:   self.heavy_attack && previous.heavy_attack == false,
+            light_kick:     self.light_kick && previous.light_kick == false,
+            medium_kick:    self.medium_kick && previous.medium_kick == false,
+            heavy_kick:     self.heavy_kick && previous.heavy_kick == false,
+            has_input:      self.has_input,
+            jump:           self.jump,
+            throw:          self.throw && previous.throw == false
+        }
+    }
+}
+
+//How many frames each step of a motion has to follow the one before it, unless the move says otherwise
+pub const MOTION_STEP_WINDOW: u32 = 10;
+//How long a direction has to be held to count as charged, unless the move says otherwise
+pub const CHARGE_FRAMES: u32 = 30;
+
+impl ScreenSideAdjustedInput {
+    //The direction being held in numpad notation, as seen by a character facing right
+    //7 8 9
+    //4 5 6
+    //1 2 3
+    pub fn numpad_direction(&self) -> u8 {
+        let row = if self.jump {
+            6
+        }
+        else if self.down_key_down {
+            0
+        }
+        else {
+            3
+        };
+
+        let column = if self.backward_down {
+            1
+        }
+        else if self.forward_down {
+            3
+        }
+        else {
+            2
+        };
+
+        return row + column;
+    }
+
+    pub fn is_button_down(&self, button: Button) -> bool {
+        match button {
+            Button::LightPunch => self.light_attack,
+            Button::MediumPunch => self.medium_attack,
+            Button::HeavyPunch => self.heavy_attack,
+            Button::LightKick => self.light_kick,
+            Button::MediumKick => self.medium_kick,
+            Button::HeavyKick => self.heavy_kick
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone, Debug)]
+pub enum Button {
+    LightPunch,
+    MediumPunch,
+    HeavyPunch,
+    LightKick,
+    MediumKick,
+    HeavyKick
+}
+
+impl Button {
+    pub fn from_notation(value: &str) -> Option<Button> {
+        match value {
+            "LP" => Some(Button::LightPunch),
+            "MP" => Some(Button::MediumPunch),
+            "HP" => Some(Button::HeavyPunch),
+            "LK" => Some(Button::LightKick),
+            "MK" => Some(Button::MediumKick),
+            "HK" => Some(Button::HeavyKick),
+            _ => None
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone, Debug)]
+pub enum MotionStep {
+    Direction(u8), //Has to pass through this direction
+    Charge(u8) //Has to have held this direction long enough before the next step, only 4 and 2 can be charged
+}
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct ButtonInput {
+    pub button: Button,
+    pub held: bool //If true the button only has to be down, otherwise it has to be pressed on this frame
+}
+
+//Tracks how long the back and down directions have been held
+//Charge moves need longer than the input history keeps, so this is counted as the inputs come in
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Default, Copy, Clone, Debug)]
+pub struct ChargeTracker {
+    pub back: DirectionCharge,
+    pub down: DirectionCharge
+}
+
+#[derive(Eq, PartialEq, Hash, Serialize, Deserialize, Default, Copy, Clone, Debug)]
+pub struct DirectionCharge {
+    pub held_frames: u32,
+    pub charge_at_release: u32, //How long it was held the last time it was let go
+    pub frames_since_release: u32
+}
+
+impl DirectionCharge {
+    pub fn update(&mut self, held: bool) {
+        if held {
+            self.held_frames = self.held_frames.saturating_add(1);
+        }
+        else if self.held_frames > 0 {
+            self.charge_at_release = self.held_frames;
+            self.held_frames = 0;
+            self.frames_since_release = 0;
+        }
+        else {
+            self.frames_since_release = self.frames_since_release.saturating_add(1);
+        }
+    }
+}
+
+impl ChargeTracker {
+    pub fn update(&mut self, input: &ScreenSideAdjustedInput) {
+        let direction = input.numpad_direction();
+        self.back.update(direction == 1 || direction == 4 || direction == 7);
+        self.down.update(direction == 1 || direction == 2 || direction == 3);
+    }
+
+    pub fn get(&self, direction: u8) -> &DirectionCharge {
+        if direction == 2 {
+            return &self.down;
+        }
+        return &self.back;
+    }
+}
+
+//How a move is entered, as it is written in the character sheet
+//notation is numpad notation, ie "236+LP", "[4]6+HP", "623+(LP)" or "66"
+//a direction in [] has to be charged, a button in () only has to be held instead of pressed
+//windows is how many frames each step has to follow the one before it, steps without one use MOTION_STEP_WINDOW
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MoveInput {
+    pub notation: String,
+    pub action: CharacterAction,
+    #[serde(default)]
+    pub windows: Vec<u32>,
+    #[serde(default = "default_charge_frames")]
+    pub charge_frames: u32
+}
+
+fn default_charge_frames() -> u32 {
+    CHARGE_FRAMES
+}
+
+impl MoveInput {
+    pub fn new(notation: &str, action: CharacterAction) -> MoveInput {
+        MoveInput {
+            notation: String::from(notation),
+            action,
+            windows: vec![],
+            charge_frames: CHARGE_FRAMES
+        }
+    }
+}
+
+//A parsed MoveInput, matched against a characters input history
+#[derive(Clone, Debug)]
+pub struct MotionPattern {
+    pub steps: Vec<MotionStep>,
+    pub windows: Vec<u32>,
+    pub buttons: Vec<ButtonInput>,
+    pub charge_frames: u32,
+    pub result_action: CharacterAction
+}
+
+impl MotionPattern {
+    pub fn from_move_input(move_input: &MoveInput) -> Result<MotionPattern, String> {
+        let mut parts = move_input.notation.split('+');
+        let motion = parts.next().unwrap_or("");
+
+        let mut steps = vec![];
+        let mut characters = motion.chars();
+        while let Some(character) = characters.next() {
+            if character == '[' {
+                let direction = characters.next().and_then(|x| x.to_digit(10));
+                if characters.next() != Some(']') {
+                    return Err(format!("{:?} has an unclosed charge", move_input.notation));
+                }
+                match direction {
+                    Some(direction) if direction == 4 || direction == 2 => {
+                        steps.push(MotionStep::Charge(direction as u8));
+                    },
+                    _ => {
+                        return Err(format!("{:?} can only charge 4 or 2", move_input.notation));
+                    }
+                }
+            }
+            else {
+                match character.to_digit(10) {
+                    Some(direction) if direction >= 1 => {
+                        steps.push(MotionStep::Direction(direction as u8));
+                    },
+                    _ => {
+                        return Err(format!("{:?} has an unknown direction {:?}", move_input.notation, character));
+                    }
+                }
+            }
+        }
+
+        let mut buttons = vec![];
+        for part in parts {
+            let held = part.starts_with('(') && part.ends_with(')');
+            let name = part.trim_start_matches('(').trim_end_matches(')');
+            match Button::from_notation(name) {
+                Some(button) => {
+                    buttons.push(ButtonInput {
+                        button,
+                        held
+                    });
+                },
+                None => {
+                    return Err(format!("{:?} has an unknown button {:?}", move_input.notation, part));
+                }
+            }
+        }
+
+        if steps.len() == 0 && buttons.len() == 0 {
+            return Err(format!("{:?} is empty", move_input.notation));
+        }
+
+        //A charge has to be released into something
+        if let Some(MotionStep::Charge(_)) = steps.last() {
+            return Err(format!("{:?} ends on a charge", move_input.notation));
+        }
+
+        let windows = (0..steps.len()).map(|x| *move_input.windows.get(x).unwrap_or(&MOTION_STEP_WINDOW)).collect();
+
+        Ok(MotionPattern {
+            steps,
+            windows,
+            buttons,
+            charge_frames: move_input.charge_frames,
+            result_action: move_input.action
+        })
+    }
+
+    //A cardinal direction right before a diagonal that includes it can be skipped, so 3 counts for 23 and 1 counts for 41
+    fn is_shortcut(direction: u8, next_step: MotionStep) -> bool {
+        match next_step {
+            MotionStep::Direction(next) => {
+                match next {
+                    1 => direction == 4 || direction == 2,
+                    3 => direction == 2 || direction == 6,
+                    7 => direction == 4 || direction == 8,
+                    9 => direction == 6 || direction == 8,
+                    _ => false
+                }
+            },
+            MotionStep::Charge(_) => false
+        }
+    }
+
+    //Checks if the move was just entered, the newest input is the last one in history
+    pub fn matches(&self, history: &[ScreenSideAdjustedInput], charge: &ChargeTracker) -> bool {
+        if history.len() == 0 {
+            return false;
+        }
+        let current = history.len() - 1;
+
+        //Presses need the frame before to be told apart from something held since earlier, without it nothing counts
+        for button_input in self.buttons.iter() {
+            if history[current].is_button_down(button_input.button) == false {
+                return false;
+            }
+            if button_input.held == false && (current == 0 || history[current - 1].is_button_down(button_input.button)) {
+                return false;
+            }
+        }
+
+        //Motions without a button happen on the frame their last direction is entered
+        if self.buttons.len() == 0 {
+            if let Some(MotionStep::Direction(direction)) = self.steps.last() {
+                if history[current].numpad_direction() != *direction 
+                    || current == 0
+                    || history[current - 1].numpad_direction() == *direction {
+                    return false;
+                }
+            }
+        }
+
+        //Walk backwards through the history looking for each step, newest step first
+        //search_from is the newest frame the step we are looking for is allowed to be on
+        let mut search_from = current as i64;
+        let mut found_at = current as i64;
+        for (index, step) in self.steps.iter().enumerate().rev() {
+            let window = self.windows[index] as i64;
+            match *step {
+                MotionStep::Direction(direction) => {
+                    if index + 1 < self.steps.len() && MotionPattern::is_shortcut(direction, self.steps[index + 1]) {
+                        //Let the step be skipped if the diagonal was used, but still use it if it was entered
+                        let mut found = false;
+                        let oldest = (search_from - window).max(0);
+                        let mut frame = search_from;
+                        while frame >= oldest {
+                            if history[frame as usize].numpad_direction() == direction {
+                                found = true;
+                                break;
+                            }
+                            frame -= 1;
+                        }
+                        if found {
+                            found_at = frame;
+                            search_from = frame - 1;
+                        }
+                        continue;
+                    }
+
+                    //A direction repeated back to back is a double tap, it has to go back to neutral in between so 636 is not 66
+                    let is_double_tap = index + 1 < self.steps.len() && self.steps[index + 1] == MotionStep::Direction(direction);
+                    let mut released = false;
+
+                    let oldest = (search_from - window).max(0);
+                    let mut frame = search_from;
+                    let mut found = false;
+                    while frame >= oldest {
+                        let held = history[frame as usize].numpad_direction();
+                        if is_double_tap {
+                            if held == 5 {
+                                released = true;
+                            }
+                            else if held != direction {
+                                break;
+                            }
+                            else if released {
+                                found = true;
+                                break;
+                            }
+                        }
+                        else if held == direction {
+                            found = true;
+                            break;
+                        }
+                        frame -= 1;
+                    }
+                    if found == false {
+                        return false;
+                    }
+                    found_at = frame;
+                    search_from = frame - 1;
+                },
+                MotionStep::Charge(direction) => {
+                    //The charge has to have been let go on or before the step after it, and not too long before
+                    let charge = charge.get(direction);
+                    let frames_ago = current as i64 - found_at;
+                    let frames_since_release = charge.frames_since_release as i64;
+                    if charge.held_frames > 0 
+                        || charge.charge_at_release < self.charge_frames 
+                        || frames_since_release < frames_ago 
+                        || frames_since_release > frames_ago + window {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        return true;
+    }
+}
+
+//The per character half of motion matching, the input history and charge
+//This lives in the Character so it is saved and rolled back with the Round, while the patterns
+//themselves are static and live in the ComboLibrary in the config
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct InputRecognizer {
+    pub past_inputs: FixedList<ScreenSideAdjustedInput, FRAME_HISTORY_LENGTH>, //A buffer that contains the last FRAME_HISTORY_LENGTH input states
+    pub charge: ChargeTracker //How long back and down have been held, for charge moves
+}
+
+impl InputRecognizer {
+    pub fn new() -> InputRecognizer {
+        InputRecognizer {
+            past_inputs: FixedList::new(),
+            charge: ChargeTracker::default()
+        }
+    }
+
+    //The input from the frame before, what presses are told apart from holds with
+    pub fn last_input(&self) -> Option<ScreenSideAdjustedInput> {
+        return self.past_inputs.last().copied();
+    }
+
+    //Adds the newest input to the history without looking for motions, for when the character can not act on them
+    pub fn record_input(&mut self, frame_input: ScreenSideAdjustedInput) {
+        self.past_inputs.push_evicting_oldest(frame_input);
+        self.charge.update(&frame_input);
+    }
+
+    //Adds the newest input and returns the first motion in the library that it completes
+    pub fn process_input(&mut self, frame_input: ScreenSideAdjustedInput, combo_library: &ComboLibrary) -> Option<CharacterAction> {
+        self.record_input(frame_input);
+
+        //Motions are checked in the order they are declared, the first one that matches wins
+        for combo in combo_library.combos.iter() {
+            if combo.matches(&self.past_inputs, &self.charge) {
+                //Clear the history so the same motion can not come out again from the same inputs, but keep the
+                //frame that finished it so the next frame can still tell a new press from a hold
+                self.past_inputs.clear();
+                self.past_inputs.push(frame_input);
+                return Some(combo.result_action);
+            }
+        }
+
+        return None;
+    }
+}
+
+//All of the special moves, dashes and other motions a character knows, in priority order
+//Nothing in here changes while a round is running
+pub struct ComboLibrary {
+    pub combos: Vec<MotionPattern>
+}
+
+impl ComboLibrary {
+    //The move_inputs come from the character sheet, a move whose notation does not parse is reported by its action
+    pub fn new(move_inputs: &[MoveInput]) -> Result<ComboLibrary, String> {
+        let mut combos = vec![];
+        for move_input in move_inputs.iter() {
+            let pattern = MotionPattern::from_move_input(move_input).map_err(|x| format!("move {:?}: {}", move_input.action, x))?;
+            combos.push(pattern);
+        }
+
+        Ok(ComboLibrary {
+            combos
+        })
+    }
+}
+
+impl Default for ComboLibrary {
+    //These are written right here and always parse
+    fn default() -> ComboLibrary {
+        ComboLibrary::new(&[
+            MoveInput::new("236+LP", CharacterAction::Special1),
+            MoveInput::new("66", CharacterAction::DashForward),
+            MoveInput::new("44", CharacterAction::DashBackward),
+            MoveInput::new("9", CharacterAction::ForwardJump)
+        ]).unwrap()
+    }
+}
+
+impl StateHash for ScreenSideAdjustedInput {
+    //Packed into one word, the history is the bulk of what gets hashed each frame
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        let buttons = [self.forward_down, self.backward_down, self.down_key_down,
+            self.light_attack, self.medium_attack, self.heavy_attack,
+            self.light_kick, self.medium_kick, self.heavy_kick,
+            self.has_input, self.jump, self.throw];
+        let mut bits = 0u32;
+        for (index, button) in buttons.iter().enumerate() {
+            bits |= (*button as u32) << index;
+        }
+        hasher.write_u32(bits);
+    }
+}
+
+impl StateHash for DirectionCharge {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u32(self.held_frames);
+        hasher.write_u32(self.charge_at_release);
+        hasher.write_u32(self.frames_since_release);
+    }
+}
+
+impl StateHash for InputRecognizer {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.past_inputs.hash_state(hasher);
+        self.charge.back.hash_state(hasher);
+        self.charge.down.hash_state(hasher);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //One frame of input written the way the sheet writes moves, "3" or "6+LP", as seen facing right
+    fn frame(notation: &str) -> ScreenSideAdjustedInput {
+        let mut parts = notation.split('+');
+        let direction = parts.next().unwrap().parse::<u8>().unwrap();
+        let mut input = ScreenSideAdjustedInput::default();
+        input.backward_down = direction % 3 == 1;
+        input.forward_down = direction % 3 == 0;
+        input.down_key_down = direction <= 3;
+        input.jump = direction >= 7;
+        for part in parts {
+            match Button::from_notation(part).unwrap() {
+                Button::LightPunch => input.light_attack = true,
+                Button::MediumPunch => input.medium_attack = true,
+                Button::HeavyPunch => input.heavy_attack = true,
+                Button::LightKick => input.light_kick = true,
+                Button::MediumKick => input.medium_kick = true,
+                Button::HeavyKick => input.heavy_kick = true
+            }
+        }
+        input.throw = input.light_attack && input.light_kick;
+        input.has_input = direction != 5 || notation.contains('+');
+        return input;
+    }
+
+    //Feeds the frames in one at a time, returning what each one came out as
+    fn run(moves: &[MoveInput], frames: &[&str]) -> Vec<Option<CharacterAction>> {
+        let library = ComboLibrary::new(moves).unwrap();
+        let mut recognizer = InputRecognizer::new();
+        return frames.iter().map(|x| recognizer.process_input(frame(x), &library)).collect();
+    }
+
+    fn repeat(notation: &'static str, count: usize) -> Vec<&'static str> {
+        return vec![notation; count];
+    }
+
+    #[test]
+    fn motions_match_on_their_last_frame() {
+        let mut charged = repeat("4", 35);
+        charged.push("6+HP");
+        let mut undercharged = repeat("4", 10);
+        undercharged.push("6+HP");
+
+        let cases: Vec<(&str, Vec<&str>, Option<CharacterAction>)> = vec![
+            ("236+LP", vec!["5", "2", "3", "6+LP"], Some(CharacterAction::Special1)),
+            ("236+LP", vec!["5", "2", "3", "6", "6+LP"], Some(CharacterAction::Special1)),
+            ("236+LP", vec!["5", "2", "6+LP"], None),
+            ("236+LP", vec!["5+LP", "2+LP", "3+LP", "6+LP"], None),
+            ("236+(LP)", vec!["5+LP", "2+LP", "3+LP", "6+LP"], Some(CharacterAction::Special1)),
+            ("[4]6+HP", charged, Some(CharacterAction::Special1)),
+            ("[4]6+HP", undercharged, None),
+            ("66", vec!["5", "6", "5", "6"], Some(CharacterAction::Special1)),
+            ("66", vec!["5", "6", "3", "6"], None),
+            ("66", vec!["5", "6", "6"], None),
+            ("9", vec!["9"], None)
+        ];
+
+        for (notation, frames, expected) in cases {
+            let results = run(&[MoveInput::new(notation, CharacterAction::Special1)], &frames);
+            assert_eq!(*results.last().unwrap(), expected, "{} on {:?}", notation, frames);
+        }
+    }
+
+    #[test]
+    fn held_direction_does_not_retrigger() {
+        let results = run(&[MoveInput::new("9", CharacterAction::ForwardJump)], &["5", "9", "9", "9", "9+LP"]);
+        assert_eq!(results, vec![None, Some(CharacterAction::ForwardJump), None, None, None]);
+    }
+
+    #[test]
+    fn held_button_is_not_a_press_after_a_match() {
+        let moves = [
+            MoveInput::new("236+LP", CharacterAction::Special1),
+            MoveInput::new("6+LP", CharacterAction::DashForward)
+        ];
+        let results = run(&moves, &["5", "2", "3", "6+LP", "6+LP", "6", "6+LP"]);
+        assert_eq!(results, vec![None, None, None, Some(CharacterAction::Special1), None, None, Some(CharacterAction::DashForward)]);
+    }
+
+    #[test]
+    fn bad_notation_is_reported_with_its_move() {
+        let moves = [
+            MoveInput::new("236+LP", CharacterAction::Special1),
+            MoveInput::new("6x6", CharacterAction::DashForward)
+        ];
+        let error = ComboLibrary::new(&moves).err().unwrap();
+        assert!(error.contains("DashForward") && error.contains("6x6"), "{}", error);
+
+        for notation in ["[6]6+HP", "[4+HP", "0+LP"].iter() {
+            assert!(ComboLibrary::new(&[MoveInput::new(notation, CharacterAction::Special1)]).is_err(), "{}", notation);
+        }
+    }
+}
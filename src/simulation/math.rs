@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use super::*;
+
+//An axis aligned bounding box, this mirrors the one storm provides so that the
+//simulation does not need to pull in the renderer just to do collision math
+//It is built on Fixed so that collisions resolve the same way on every platform
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AABB2D {
+    pub min: FixedVector2,
+    pub max: FixedVector2,
+}
+
+impl AABB2D {
+    pub fn new(minx: Fixed, miny: Fixed, maxx: Fixed, maxy: Fixed) -> AABB2D {
+        AABB2D {
+            min: FixedVector2::new(minx, miny),
+            max: FixedVector2::new(maxx, maxy),
+        }
+    }
+
+    //Collision boxes are authored in whole pixels
+    pub fn from_ints(minx: i32, miny: i32, maxx: i32, maxy: i32) -> AABB2D {
+        AABB2D::new(Fixed::from_int(minx), Fixed::from_int(miny), Fixed::from_int(maxx), Fixed::from_int(maxy))
+    }
+
+    //Moves the box so it is relative to position instead of to the frame
+    pub fn translate(&self, position: FixedVector2) -> AABB2D {
+        AABB2D {
+            min: self.min + position,
+            max: self.max + position,
+        }
+    }
+
+    pub fn contains_point(&self, point: &FixedVector2) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x && point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    pub fn intersects(&self, other: &AABB2D) -> bool {
+        self.min.x < other.max.x && self.max.x > other.min.x && self.min.y < other.max.y && self.max.y > other.min.y
+    }
+
+    //Moves the box by movement, stopping it flush against any of the others it would run into
+    //Returns true if the movement was cut short by one of the others
+    pub fn slide(&mut self, movement: &FixedVector2, others: &[AABB2D]) -> bool {
+        if movement.x == Fixed::ZERO && movement.y == Fixed::ZERO {
+            return false;
+        }
+        let mut collided = false;
+
+        let mut x = movement.x;
+        if x > Fixed::ZERO {
+            for other in others {
+                if self.max.y > other.min.y && self.min.y < other.max.y && self.max.x <= other.min.x {
+                    let distance = other.min.x - self.max.x;
+                    if distance < x {
+                        x = distance;
+                        collided = true;
+                    }
+                }
+            }
+        }
+        else if x < Fixed::ZERO {
+            for other in others {
+                if self.max.y > other.min.y && self.min.y < other.max.y && self.min.x >= other.max.x {
+                    let distance = other.max.x - self.min.x;
+                    if distance > x {
+                        x = distance;
+                        collided = true;
+                    }
+                }
+            }
+        }
+        self.min.x += x;
+        self.max.x += x;
+
+        let mut y = movement.y;
+        if y > Fixed::ZERO {
+            for other in others {
+                if self.max.x > other.min.x && self.min.x < other.max.x && self.max.y <= other.min.y {
+                    let distance = other.min.y - self.max.y;
+                    if distance < y {
+                        y = distance;
+                        collided = true;
+                    }
+                }
+            }
+        }
+        else if y < Fixed::ZERO {
+            for other in others {
+                if self.max.x > other.min.x && self.min.x < other.max.x && self.min.y >= other.max.y {
+                    let distance = other.max.y - self.min.y;
+                    if distance > y {
+                        y = distance;
+                        collided = true;
+                    }
+                }
+            }
+        }
+        self.min.y += y;
+        self.max.y += y;
+
+        return collided;
+    }
+}
+
+pub trait Reflect {
+    fn reflect(&self, x_axis: usize) -> AABB2D;
+}
+
+impl Reflect for AABB2D {
+    fn reflect(&self, x_axis: usize) -> AABB2D {
+        let x_axis = Fixed::from_int(x_axis as i32);
+        let min_dif = self.min.x - x_axis;
+        let max_dif = self.max.x - x_axis;
+        AABB2D::new(
+            self.min.x - (min_dif + max_dif),
+            self.min.y,
+            self.max.x - (min_dif + max_dif),
+            self.max.y)
+    }
+}
@@ -0,0 +1,33 @@
+mod animation;
+mod character;
+mod round;
+mod input;
+mod collision;
+mod projectile;
+mod character_sheet;
+mod effects;
+mod math;
+mod config;
+mod fixed;
+mod hit_registry;
+mod combo_counter;
+mod fixed_list;
+mod state_hash;
+mod replay;
+
+pub use animation::*;
+pub use character::*;
+pub use round::*;
+pub use collision::*;
+pub use input::*;
+pub use projectile::*;
+pub use character_sheet::*;
+pub use effects::*;
+pub use math::*;
+pub use config::*;
+pub use fixed::*;
+pub use hit_registry::*;
+pub use combo_counter::*;
+pub use fixed_list::*;
+pub use state_hash::*;
+pub use replay::*;
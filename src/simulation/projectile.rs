@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ScreenSide, AnimationPlayer, FixedVector2, HitProperties, HitRegistry, StateHash, StateHasher};
+
+const PROJECTILE_FRAME_LENGTH : u32 = 4;
+const PROJECTILE_FRAME_COUNT : u32 = 20;
+
+#[derive(Serialize, Deserialize, Copy, Clone)]
+pub struct Projectile {
+    pub position: FixedVector2,
+    pub velocity: FixedVector2,
+    pub screen_side: ScreenSide,
+    pub timer: AnimationPlayer,
+    pub team: usize,
+    pub hit_properties: HitProperties, //Copied from the move that threw it
+    pub hit_registry: HitRegistry
+}
+
+impl Projectile {
+    pub fn new(    
+        position: FixedVector2,
+        velocity: FixedVector2,
+        screen_side: ScreenSide,
+        team: usize,
+        hit_properties: HitProperties
+    ) -> Projectile {
+        Projectile {
+            position,
+            velocity,
+            screen_side,
+            timer: AnimationPlayer::new(PROJECTILE_FRAME_LENGTH),
+            team,
+            hit_properties,
+            hit_registry: HitRegistry::new()
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.position += self.velocity;
+        self.hit_registry.tick();
+        if self.timer.sprite_timer.finished() {
+            self.timer.sprite_timer.reset();
+            self.timer.current_frame += 1;
+            //If we have finished the animation move the character into the
+            //next state, be that loop(like idle or run)
+            //or a steady state like Attack -> Idle
+            if self.timer.is_done(PROJECTILE_FRAME_COUNT) {
+                self.timer.reset();
+            }
+        }
+    }
+}
+
+impl Default for Projectile {
+    fn default() -> Projectile {
+        return Projectile::new(FixedVector2::zero(), FixedVector2::zero(), ScreenSide::Right, 0, HitProperties::default());
+    }
+}
+
+impl StateHash for Projectile {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.position.hash_state(hasher);
+        self.velocity.hash_state(hasher);
+        hasher.write_u32(self.screen_side as u32);
+        self.timer.hash_state(hasher);
+        hasher.write_usize(self.team);
+        self.hit_properties.hash_state(hasher);
+        self.hit_registry.hash_state(hasher);
+    }
+}
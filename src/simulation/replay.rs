@@ -0,0 +1,301 @@
+use serde::{Deserialize, Serialize};
+use super::*;
+
+pub const GAME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+//Every confirmed frame of inputs for both players, from the first frame of the match
+//Feeding these back through Round::advance_match rebuilds the whole match
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Replay {
+    pub game_version: String,
+    pub config_hash: u64, //The GameplayConfig it was recorded with, it will not play back the same on any other
+    pub palletes: [usize; 2], //Which pallete each player was using
+    pub inputs: Vec<[NetInput; 2]>
+}
+
+impl Replay {
+    pub fn new(game_config: &GameplayConfig, palletes: [usize; 2]) -> Replay {
+        Replay {
+            game_version: String::from(GAME_VERSION),
+            config_hash: game_config.config_hash,
+            palletes,
+            inputs: vec![]
+        }
+    }
+
+    pub fn record(&mut self, inputs: [NetInput; 2]) {
+        self.inputs.push(inputs);
+    }
+
+    //A different sheet means different frame data, so the recorded inputs would not play out the same match
+    pub fn check_config(&self, game_config: &GameplayConfig) -> Result<(), String> {
+        if self.config_hash != game_config.config_hash {
+            return Err(format!("replay was recorded with config {:016x} (version {}) but this is config {:016x} (version {})",
+                self.config_hash, self.game_version, game_config.config_hash, GAME_VERSION));
+        }
+        return Ok(());
+    }
+
+    //The Round the match starts on, the same one the game builds when it loads
+    pub fn starting_round(game_config: &GameplayConfig) -> Round {
+        return Round::new_with_animation_lib(&game_config.animation_configs);
+    }
+
+    //Re-runs the match, handing back the state hash after every frame
+    //Index 0 is the hash after the first frame, the same numbering as the session frame
+    pub fn checksums(&self, game_config: &GameplayConfig) -> Vec<u64> {
+        let mut round = Replay::starting_round(game_config);
+        let mut checksums = Vec::with_capacity(self.inputs.len());
+        for inputs in self.inputs.iter() {
+            round.advance_match(*inputs, game_config);
+            checksums.push(round.state_hash());
+        }
+        return checksums;
+    }
+}
+
+//A checksum stream is one "<frame> <checksum in hex>" pair per line, lines starting with # are comments
+//round_diff reads and writes these, and they are what the golden replay files hold
+pub fn format_checksum_stream(checksums: &[u64]) -> String {
+    let mut text = String::new();
+    for (index, checksum) in checksums.iter().enumerate() {
+        text.push_str(&format!("{} {:016x}\n", index + 1, checksum));
+    }
+    return text;
+}
+
+//Sorted by frame, the frames do not need to be contiguous
+pub fn parse_checksum_stream(text: &str) -> Result<Vec<(usize, u64)>, String> {
+    let mut checksums = vec![];
+    for (line_number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let frame = parts.next().and_then(|x| x.parse::<usize>().ok());
+        let checksum = parts.next().and_then(|x| u64::from_str_radix(x.trim_start_matches("0x"), 16).ok());
+        match (frame, checksum) {
+            (Some(frame), Some(checksum)) => checksums.push((frame, checksum)),
+            _ => return Err(format!("line {}: expected \"<frame> <checksum>\"", line_number + 1))
+        }
+    }
+    checksums.sort_by_key(|x| x.0);
+    return Ok(checksums);
+}
+
+//How often the ReplayPlayer keeps a copy of the Round, seeking re-simulates at most this many frames
+pub const REPLAY_SNAPSHOT_INTERVAL: usize = 120;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReplayMarkerKind {
+    RoundStart,
+    Hit //Anything that put the game into hit stop or took health off, blocks and parries included
+}
+
+//A frame worth jumping to, the frame is the number of replay frames played to get to it
+#[derive(Copy, Clone, Debug)]
+pub struct ReplayMarker {
+    pub frame: usize,
+    pub kind: ReplayMarkerKind
+}
+
+//Steps a Round through a Replay, no network session involved
+//Frames are always simulated in order from the start, so everything up to furthest_frame has been seen
+//and has its snapshots and markers, seeking backwards restores a snapshot and plays forward from there
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    pub frame: usize, //How many frames of the replay have been played
+    pub furthest_frame: usize, //The furthest the replay has been played, snapshots and markers cover up to here
+    pub snapshots: Vec<(usize, Round)>, //One every REPLAY_SNAPSHOT_INTERVAL frames, starting with frame 0
+    pub markers: Vec<ReplayMarker>
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay, game_config: &GameplayConfig) -> Result<ReplayPlayer, String> {
+        replay.check_config(game_config)?;
+        Ok(ReplayPlayer {
+            replay,
+            frame: 0,
+            furthest_frame: 0,
+            snapshots: vec![(0, Replay::starting_round(game_config))],
+            markers: vec![ReplayMarker { frame: 0, kind: ReplayMarkerKind::RoundStart }]
+        })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        return self.frame >= self.replay.inputs.len();
+    }
+
+    pub fn frame_count(&self) -> usize {
+        return self.replay.inputs.len();
+    }
+
+    //Returns false once there is nothing left to play
+    pub fn step(&mut self, round: &mut Round, game_config: &GameplayConfig) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        let previous = *round;
+        round.advance_match(self.replay.inputs[self.frame], game_config);
+        self.frame += 1;
+
+        if self.frame > self.furthest_frame {
+            self.furthest_frame = self.frame;
+            self.record_markers(&previous, round);
+            if self.frame % REPLAY_SNAPSHOT_INTERVAL == 0 {
+                self.snapshots.push((self.frame, *round));
+            }
+        }
+        return true;
+    }
+
+    fn record_markers(&mut self, previous: &Round, round: &Round) {
+        //advance_match swaps in a fresh Round when one ends, which is the only time the round frame goes backwards
+        if round.frame < previous.frame {
+            self.markers.push(ReplayMarker { frame: self.frame, kind: ReplayMarkerKind::RoundStart });
+            return;
+        }
+
+        let lost_health = (0..2).any(|x| round.characters[x].health < previous.characters[x].health);
+        if round.hit_stun_counter > previous.hit_stun_counter || lost_health {
+            self.markers.push(ReplayMarker { frame: self.frame, kind: ReplayMarkerKind::Hit });
+        }
+    }
+
+    //Puts the round on the given frame, clamped to the length of the replay
+    //Going backwards, or further forward than the next snapshot, restores the closest snapshot at or before the frame first
+    pub fn seek(&mut self, target_frame: usize, round: &mut Round, game_config: &GameplayConfig) {
+        let target_frame = target_frame.min(self.frame_count());
+        let index = self.snapshots.iter().rposition(|x| x.0 <= target_frame).unwrap();
+        let (snapshot_frame, snapshot) = self.snapshots[index];
+        if target_frame < self.frame || snapshot_frame > self.frame {
+            *round = snapshot;
+            self.frame = snapshot_frame;
+        }
+
+        while self.frame < target_frame {
+            self.step(round, game_config);
+        }
+    }
+
+    //Moves by a number of frames, negative goes backwards
+    pub fn seek_by(&mut self, frames: i64, round: &mut Round, game_config: &GameplayConfig) {
+        let target_frame = (self.frame as i64 + frames).max(0) as usize;
+        self.seek(target_frame, round, game_config);
+    }
+
+    //Jumps to the next marker of the kind, playing on past furthest_frame to find one if we have not seen it yet
+    //Stays where it is and returns false if there is not one before the end of the replay
+    pub fn seek_to_next_marker(&mut self, kind: ReplayMarkerKind, round: &mut Round, game_config: &GameplayConfig) -> bool {
+        let start_frame = self.frame;
+        if let Some(marker) = self.markers.iter().find(|x| x.kind == kind && x.frame > start_frame) {
+            let marker_frame = marker.frame;
+            self.seek(marker_frame, round, game_config);
+            return true;
+        }
+
+        let furthest_frame = self.furthest_frame;
+        self.seek(furthest_frame, round, game_config);
+        let marker_count = self.markers.len();
+        while self.step(round, game_config) {
+            if self.markers[marker_count..].iter().any(|x| x.kind == kind) {
+                return true;
+            }
+        }
+
+        self.seek(start_frame, round, game_config);
+        return false;
+    }
+
+    //Jumps back to the last marker of the kind before the current frame
+    pub fn seek_to_previous_marker(&mut self, kind: ReplayMarkerKind, round: &mut Round, game_config: &GameplayConfig) -> bool {
+        let start_frame = self.frame;
+        if let Some(marker) = self.markers.iter().rev().find(|x| x.kind == kind && x.frame < start_frame) {
+            let marker_frame = marker.frame;
+            self.seek(marker_frame, round, game_config);
+            return true;
+        }
+        return false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gameplay_config() -> GameplayConfig {
+        let character_sheet: CharacterSheet = serde_json::from_str(include_str!("../../resources/ryu_character_sheet.json")).unwrap();
+        return GameplayConfig::new_from_sheet(character_sheet).unwrap();
+    }
+
+    //Button mashing that is the same every run, long enough to cover a few snapshots
+    fn mashed_replay(game_config: &GameplayConfig, frames: usize) -> Replay {
+        let mut replay = Replay::new(game_config, [0; 2]);
+        let mut seed: u32 = 1;
+        for _ in 0..frames {
+            let mut inputs = [NetInput { input: 0 }; 2];
+            for input in inputs.iter_mut() {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                input.input = ((seed >> 16) & 0x3ff) as u16;
+            }
+            replay.record(inputs);
+        }
+        return replay;
+    }
+
+    //The hash the round should have after playing a number of frames, playing from the start every time
+    fn expected_hash(checksums: &[u64], game_config: &GameplayConfig, frame: usize) -> u64 {
+        if frame == 0 {
+            return Replay::starting_round(game_config).state_hash();
+        }
+        return checksums[frame - 1];
+    }
+
+    #[test]
+    fn seeking_lands_on_the_same_round_as_playing_through() {
+        let game_config = gameplay_config();
+        let replay = mashed_replay(&game_config, REPLAY_SNAPSHOT_INTERVAL * 4 + 17);
+        let checksums = replay.checksums(&game_config);
+        let mut player = ReplayPlayer::new(replay, &game_config).unwrap();
+        let mut round = Replay::starting_round(&game_config);
+
+        //Forward past a few snapshots, back to before the latest one, forward again over ground already covered,
+        //and onto the exact frames snapshots are taken on
+        let targets = [
+            REPLAY_SNAPSHOT_INTERVAL * 3 + 5,
+            10,
+            REPLAY_SNAPSHOT_INTERVAL * 2,
+            REPLAY_SNAPSHOT_INTERVAL * 2 - 1,
+            REPLAY_SNAPSHOT_INTERVAL * 3 + 5,
+            REPLAY_SNAPSHOT_INTERVAL * 4 + 17,
+            0,
+            REPLAY_SNAPSHOT_INTERVAL
+        ];
+        for target in targets.iter() {
+            player.seek(*target, &mut round, &game_config);
+            assert_eq!(player.frame, *target);
+            assert_eq!(round.state_hash(), expected_hash(&checksums, &game_config, *target), "seeking to frame {}", target);
+        }
+        assert_eq!(player.furthest_frame, player.frame_count());
+        assert_eq!(player.snapshots.len(), 5);
+    }
+
+    #[test]
+    fn seeking_is_clamped_to_the_replay() {
+        let game_config = gameplay_config();
+        let replay = mashed_replay(&game_config, 50);
+        let checksums = replay.checksums(&game_config);
+        let mut player = ReplayPlayer::new(replay, &game_config).unwrap();
+        let mut round = Replay::starting_round(&game_config);
+
+        player.seek(1000, &mut round, &game_config);
+        assert_eq!(player.frame, 50);
+        assert!(player.is_finished());
+        assert_eq!(round.state_hash(), checksums[49]);
+
+        player.seek_by(-1000, &mut round, &game_config);
+        assert_eq!(player.frame, 0);
+        assert_eq!(round.state_hash(), expected_hash(&checksums, &game_config, 0));
+    }
+}
@@ -0,0 +1,794 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use super::*;
+
+const FRAME_WIDTH_FIXED : Fixed = Fixed::from_int(FRAME_WIDTH as i32);
+const MAX_PLAYER_DISTANCE : Fixed = FRAME_WIDTH_FIXED;
+const STAGE_WIDTH : Fixed = Fixed::from_int(896);
+const JUMP_VELOCITY : Fixed = Fixed::from_int(15);
+const FORWARD_JUMP_GRAVITY : Fixed = Fixed::ONE;
+const JUMP_GRAVITY : Fixed = Fixed::from_ratio(6, 5);
+const AIR_HIT_GRAVITY : Fixed = Fixed::ONE;
+//How far up a knockdown, or any hit on an airborne character, pops them
+const AIR_HIT_POP_VELOCITY : Fixed = Fixed::from_int(6);
+
+//The Round never allocates, so there is a hard cap on how much can be on screen at once
+pub const MAX_PROJECTILES : usize = 8;
+pub const MAX_EFFECTS : usize = 16;
+
+const THROW_TECH_PUSHBACK_FRAMES : u32 = 10;
+const THROW_TECH_PUSHBACK : Fixed = Fixed::from_int(6);
+
+//Parries are not authored per move, being parried always costs the same
+const PARRY_HIT_PROPERTIES : HitProperties = HitProperties {
+    damage: 5,
+    chip_damage: 0,
+    hitstun: 0,
+    blockstun: 0,
+    hit_stop: 6,
+    pushback: Fixed::ZERO,
+    stun: 10,
+    guard: GuardType::Mid,
+    hits: 1,
+    hit_interval: 0,
+    reaction: HitReaction::Stagger,
+    launch_velocity: Fixed::ZERO
+};
+
+//Everything in here is fixed size and Copy, saving it for a rollback is a single memcpy
+#[derive(Serialize, Deserialize, Copy, Clone)]
+pub struct Round {
+    pub characters: [Character; 2],
+    pub frame: i32,
+    pub round_timer: SpriteTimer,
+    pub round_done: bool,
+    pub hit_stun_counter: usize,
+    pub projectiles: FixedList<Projectile, MAX_PROJECTILES>,
+    pub reset_round_timer: SpriteTimer,
+    pub effects: FixedList<Effect, MAX_EFFECTS>,
+    pub combo_counters: [ComboCounter; 2], //Indexed by the character landing the combo
+    //state_machine: StateMachine,
+}
+
+impl Round {
+    pub fn advance(&mut self, inputs: [NetInput; 2], game_config: &GameplayConfig) {
+        for effect in self.effects.iter_mut() {
+            effect.advance();
+        }
+        self.effects.retain(|x|x.is_use);
+
+        self.frame += 1;
+        if self.hit_stun_counter > 0 {
+            self.hit_stun_counter -= 1;
+            return;
+        }
+
+        if self.characters[0].character_position.x > self.characters[1].character_position.x {
+            self.characters[0].screen_side = ScreenSide::Right;
+            self.characters[1].screen_side = ScreenSide::Left;
+        }
+        else {
+            self.characters[0].screen_side = ScreenSide::Left;
+            self.characters[1].screen_side = ScreenSide::Right;
+        }
+        
+        self.character_tick(0, Input::from_game_input(inputs[0]), game_config);
+        self.character_tick(1, Input::from_game_input(inputs[1]), game_config);
+
+        //A combo ends as soon as the character taking it gets out of hitstun
+        for attacker in 0..2 {
+            if self.combo_counters[attacker].in_progress && self.characters[1 - attacker].is_in_hitstun() == false {
+                self.combo_counters[attacker].drop_combo();
+            }
+            self.combo_counters[attacker].tick();
+        }
+
+        for projectile in self.projectiles.iter_mut() {
+            projectile.tick();
+        }
+
+        //Leave early if we are wating for a round to fully end for the reset
+        if self.round_done {
+            let (won, lost) = self.who_won_who_lost();
+            self.characters[won].set_character_state(CharacterState::Won, &game_config);
+            self.characters[won].done = true;
+            self.characters[lost].set_character_state(CharacterState::Lost, &game_config);
+            self.characters[lost].done = true;
+            self.reset_round_timer.tick();
+            return;
+        }
+
+        self.round_timer.tick();
+
+        let walk_box_offset = FixedVector2::new(Fixed::from_int(WALK_BOX_OFFSET_X), Fixed::from_int(WALK_BOX_OFFSET_Y));
+        let mut character_1_walk_box = self.characters[0].get_walk_box();
+        let mut character_2_walk_box = self.characters[1].get_walk_box();
+
+        //TODO: these two functions are very fragile, would like to refactor them
+        //into a single funciton on character
+        if self.characters[0].character_velocity != FixedVector2::zero() {
+            //let mut reshift = Vector2::new(0.0, 0.0);
+            if self.characters[0].is_airborne() {
+                if character_1_walk_box.slide(&self.characters[0].character_velocity, &[]) {
+                    //Overlap. hmmmm
+
+                    //    reshift.x = CHARACTER_X_SPEED * 1.1 * self.characters[0].screen_side.direction() * -1.0;
+                }
+            }
+            else { 
+                if character_1_walk_box.slide(&self.characters[0].character_velocity, &[character_2_walk_box]) {
+                    //Overlap. hmmmm
+                }
+            }
+
+            //We need to remove the offset that we build in from the initial unshifted AABBS
+            //This will give us the characters new position
+            self.characters[0].character_position = character_1_walk_box.min - walk_box_offset;// + reshift;
+            if self.characters[0].character_position.y < Fixed::ZERO {
+                self.characters[0].character_position.y = Fixed::ZERO;
+            }
+
+            //Keep the character in the arena, and close enough to the other playear
+            if self.characters[0].character_position.x  < Fixed::ZERO  {
+                self.characters[0].character_position.x = Fixed::ZERO;
+            }
+            else if self.characters[0].character_position.x + FRAME_WIDTH_FIXED >= STAGE_WIDTH { 
+                self.characters[0].character_position.x = STAGE_WIDTH - FRAME_WIDTH_FIXED;
+            }
+            else if (self.characters[0].character_position.x - self.characters[1].character_position.x).abs() > MAX_PLAYER_DISTANCE {
+                //TODO: Handle screen sides when we add in jumping
+                
+                self.characters[0].character_position.x = self.characters[1].character_position.x + MAX_PLAYER_DISTANCE;
+            }
+        }
+
+
+        if self.characters[1].character_velocity != FixedVector2::zero() {
+            //let mut reshift = Vector2::new(0.0, 0.0);
+            if self.characters[1].is_airborne() {
+                if character_2_walk_box.slide(&self.characters[1].character_velocity, &[]) {
+                    //Overlap. hmmmm
+                 //   reshift.x = CHARACTER_X_SPEED * 1.1 * self.characters[1].screen_side.direction() * -1.0;
+                }
+            }
+            else { 
+                if character_2_walk_box.slide(&self.characters[1].character_velocity, &[character_1_walk_box]) {
+                    //Overlap. hmmmm
+
+                }
+            }
+            //We need to remove the offset that we build in from the initial unshifted AABBS
+            //This will give us the characters new position
+            self.characters[1].character_position = character_2_walk_box.min - walk_box_offset;// + reshift;
+            if self.characters[1].character_position.y < Fixed::ZERO {
+                self.characters[1].character_position.y = Fixed::ZERO;
+            }
+
+            if self.characters[1].character_position.x  < Fixed::ZERO  {
+                self.characters[1].character_position.x = Fixed::ZERO;
+            }
+            else if self.characters[1].character_position.x + FRAME_WIDTH_FIXED >= STAGE_WIDTH { 
+                self.characters[1].character_position.x = STAGE_WIDTH - FRAME_WIDTH_FIXED;
+            }
+            else if (self.characters[0].character_position.x - self.characters[1].character_position.x).abs() > MAX_PLAYER_DISTANCE {
+                //TODO: Handle screen sides when we add in jumping
+                //Maybe add in jumping tonight???__??
+                self.characters[1].character_position.x = self.characters[0].character_position.x - MAX_PLAYER_DISTANCE;
+            }
+
+        }     
+
+        let character_1_collision_key = self.characters[0].get_collision_box_lookup_info();
+        let current_aabbs_for_character_1 = game_config.collision_library.collision_info.get(&character_1_collision_key.0).unwrap().frame_collision.get(&character_1_collision_key.1).unwrap();
+
+        let character_2_collision_key = self.characters[1].get_collision_box_lookup_info();
+        let current_aabbs_for_character_2 = game_config.collision_library.collision_info.get(&character_2_collision_key.0).unwrap().frame_collision.get(&character_2_collision_key.1).unwrap();
+
+        //These two forloops for the same thing, just with a different character, they create new AABBS
+        //Using the dimensions of the ones from the library, shifted by the characters position 
+        let mut character_1_position_corrected_aabbs = vec![];
+        for cb in current_aabbs_for_character_1 {
+            let mut use_aabb = cb.aabb;
+            if self.characters[0].screen_side == ScreenSide::Left {
+                use_aabb = use_aabb.reflect((FRAME_WIDTH / 2) as usize);
+             }
+            //Do the shift
+            let aabb = use_aabb.translate(self.characters[0].character_position);
+            character_1_position_corrected_aabbs.push((aabb, cb.box_type));
+        }
+
+        let mut character_2_position_corrected_aabbs = vec![];
+        for cb in current_aabbs_for_character_2 {
+            let mut use_aabb = cb.aabb;
+            if self.characters[1].screen_side == ScreenSide::Left {
+                use_aabb = use_aabb.reflect((FRAME_WIDTH / 2) as usize);
+             }
+            //Do the shift
+            let aabb = use_aabb.translate(self.characters[1].character_position);
+            //Stick it into a vec for use later
+            character_2_position_corrected_aabbs.push((aabb, cb.box_type));
+        }
+
+        //Get just the hurt and throw boxes, these are the ones that can do something to the other character
+        let character_1_hurt_boxes : Vec<_> = character_1_position_corrected_aabbs.iter().filter(|x|{
+            return x.1 == CollisionBoxType::Hurt || x.1 == CollisionBoxType::Throw;
+        }).collect();
+
+        let character_2_hurt_boxes : Vec<_> = character_2_position_corrected_aabbs.iter().filter(|x|{
+            return x.1 == CollisionBoxType::Hurt || x.1 == CollisionBoxType::Throw;
+        }).collect();
+
+        //For each characters hurt boxes, check them against the other characters total set of Hurt and Hit Boxes
+        //TODO: aabb.0.x needs to be changed to maybe something between characters
+        let mut collision_reports = vec![];
+        for hurt_box in character_1_hurt_boxes {
+            for aabb in character_2_position_corrected_aabbs.iter() {
+                if hurt_box.0.intersects(&aabb.0) {
+                    let collision_report = CollisionReport::new(hurt_box.1, aabb.1, CharacterNumber::Number1, aabb.0.min.x);
+                    collision_reports.push(collision_report);
+                }
+            }
+        }
+        
+        for hurt_box in character_2_hurt_boxes {
+            for aabb in character_1_position_corrected_aabbs.iter() {
+                if hurt_box.0.intersects(&aabb.0) {
+                    let collision_report = CollisionReport::new(hurt_box.1, aabb.1, CharacterNumber::Number2, aabb.0.min.x);
+                    collision_reports.push(collision_report);
+                }
+            }
+        }
+
+
+        //If a parry box hit a 
+        let parries = collision_reports.iter().filter(|x|{
+            return x.collider_type == CollisionBoxType::Hurt && x.collide_type == CollisionBoxType::Hurt;
+        });
+
+        //Strikes are Hurt on Hit boxes
+        let strikes = collision_reports.iter().filter(|x|{
+            return x.collider_type == CollisionBoxType::Hurt && x.collide_type == CollisionBoxType::Hit;
+        });
+
+        //Throws are Throw on Hit boxes
+        let mut character_1_throws = false;
+        let mut character_2_throws = false;
+        for throw in collision_reports.iter().filter(|x| x.collider_type == CollisionBoxType::Throw && x.collide_type == CollisionBoxType::Hit) {
+            match throw.collider_character {
+                CharacterNumber::Number1 => {
+                    character_1_throws = self.characters[1].is_throwable();
+                },
+                CharacterNumber::Number2 => {
+                    character_2_throws = self.characters[0].is_throwable();
+                }
+            }
+        }
+
+        //If both characters grab each other on the same frame it is an automatic tech
+        if character_1_throws && character_2_throws {
+            self.tech_throw(game_config);
+            return;
+        }
+        else if character_1_throws {
+            self.start_throw(0, 1, game_config);
+            return;
+        }
+        else if character_2_throws {
+            self.start_throw(1, 0, game_config);
+            return;
+        }
+
+
+        //TODO: handle parries, idk, things do 
+        //Each attack keeps a registry of who it has hit, so overlapping boxes and long active windows
+        //only land as many times as the move allows
+        //Invulnerability is per target, a character on the ground or past the juggle limit can not be hit
+        //but the other character, and everything else in the round, carries on as normal
+        for par in parries {
+            let (attacker, target) = match par.collider_character {
+                CharacterNumber::Number1 => (0, 1),
+                CharacterNumber::Number2 => (1, 0)
+            };
+            if self.characters[target].is_in_damageable_state() == false {
+                continue;
+            }
+            if self.characters[attacker].hit_registry.can_hit(target, &PARRY_HIT_PROPERTIES) {
+                self.characters[attacker].hit_registry.register_hit(target, &PARRY_HIT_PROPERTIES);
+                self.do_damage_to_character(target, &PARRY_HIT_PROPERTIES, true, game_config);
+                self.hit_stun_counter += PARRY_HIT_PROPERTIES.hit_stop as usize;
+            }
+        }
+
+        //Preform strikes and assign damage
+        for strike in strikes {
+            let (attacker, target) = match strike.collider_character {
+                CharacterNumber::Number1 => (0, 1),
+                CharacterNumber::Number2 => (1, 0)
+            };
+            if self.characters[target].is_in_damageable_state() == false {
+                continue;
+            }
+            let hit_properties = self.characters[attacker].get_current_hit_properties(game_config);
+            if self.characters[attacker].hit_registry.can_hit(target, &hit_properties) {
+                self.characters[attacker].hit_registry.register_hit(target, &hit_properties);
+
+                let effect = Effect::new(3,  10, EffectKind::Low, self.characters[target].character_position.x, Fixed::ZERO, ScreenSide::Right);
+                self.effects.push(effect);
+
+                self.do_damage_to_character(target, &hit_properties, false, game_config);
+                self.hit_stun_counter += hit_properties.hit_stop as usize;
+
+                if self.characters[target].character_state == CharacterState::Blocking {
+                    self.characters[attacker].move_contact = MoveContact::Block;
+                }
+                else {
+                    self.characters[attacker].move_contact = MoveContact::Hit;
+                }
+            }
+        }
+        
+        let fireball_collision = &game_config.collision_library.fireball_collision;
+        let mut projectile_position_corrected_aabbs = vec![];
+        for projectile in self.projectiles.iter() {
+            let mut use_aabb = fireball_collision.frame_collision.get(&0).unwrap()[0].aabb;
+            if projectile.screen_side == ScreenSide::Left {
+                use_aabb = use_aabb.reflect((FRAME_WIDTH / 2) as usize);
+             }
+            //Do the shift
+            let aabb = use_aabb.translate(projectile.position);
+            projectile_position_corrected_aabbs.push(aabb);
+        }
+
+        for (index, projectile_aabb) in projectile_position_corrected_aabbs.iter().enumerate() {
+            let target;
+            let target_aabbs;
+            if self.projectiles[index].team == 0 {
+                target = 1;
+                target_aabbs = &character_2_position_corrected_aabbs;
+            }
+            else {
+                target = 0;
+                target_aabbs = &character_1_position_corrected_aabbs;
+            }
+
+            //Projectiles pass through anyone who can not be hit, and keep going
+            let touching = self.characters[target].is_in_damageable_state() && target_aabbs.iter().any(|aabb| aabb.1 != CollisionBoxType::Throw && projectile_aabb.intersects(&aabb.0));
+            let hit_properties = self.projectiles[index].hit_properties;
+            if touching && self.projectiles[index].hit_registry.can_hit(target, &hit_properties) {
+                self.projectiles[index].hit_registry.register_hit(target, &hit_properties);
+                self.do_damage_to_character(target, &hit_properties, false, game_config);
+                self.hit_stun_counter += hit_properties.hit_stop as usize;
+            }
+        }
+
+        //Projectiles that have landed all of their hits are done
+        self.projectiles.retain(|x|x.hit_registry.is_spent(&x.hit_properties) == false);
+
+        //Projectiles that have left the stage can never hit anyone, and letting them fly forever
+        //would eventually overflow their fixed point position
+        self.projectiles.retain(|x|x.position.x > -STAGE_WIDTH && x.position.x < STAGE_WIDTH + STAGE_WIDTH);
+
+        //If either player has died
+        if self.characters[0].health == 0 || self.characters[1].health == 0 {
+            self.round_done = true;
+        }
+        //Or we have just finished the game
+        else if self.round_timer.finished() {
+            self.round_done = true;
+        }
+
+    }
+
+    pub fn who_won_who_lost(&self) -> (usize, usize) {
+        if self.characters[0].health > self.characters[1].health {
+            return (0, 1);
+        }
+        return (1, 0);
+    }
+
+    pub fn character_tick(&mut self, character_index: usize, frame_input: Input, game_config: &GameplayConfig) {
+
+        self.characters[character_index].hit_registry.tick();
+
+        //Characters knocked into the air are put on the ground once they land
+        if self.characters[character_index].character_state == CharacterState::AirHit
+            && self.characters[character_index].character_position.y <= Fixed::ZERO
+            && self.characters[character_index].character_velocity.y < Fixed::ZERO {
+            self.characters[character_index].character_position.y = Fixed::ZERO;
+            self.characters[character_index].character_velocity = FixedVector2::zero();
+            self.characters[character_index].juggle_count = 0;
+            self.characters[character_index].is_crouched = false;
+            self.characters[character_index].rise_chosen = false;
+            self.characters[character_index].set_character_state(CharacterState::KnockedDown, &game_config);
+            self.characters[character_index].recovery_frames = KNOCKDOWN_FRAMES;
+        }
+
+        //Count down any hitstun or blockstun left over from the last hit, once it runs out the character is free to act
+        if self.characters[character_index].recovery_frames > 0 {
+            self.characters[character_index].recovery_frames -= 1;
+            if self.characters[character_index].recovery_frames == 0 {
+                self.characters[character_index].pushback = Fixed::ZERO;
+                let character_state = self.characters[character_index].character_state;
+                if character_state == CharacterState::LightHitRecovery || character_state == CharacterState::Blocking {
+                    self.characters[character_index].set_character_state(CharacterState::Idle, &game_config);
+                }
+                else if character_state == CharacterState::KnockedDown {
+                    self.characters[character_index].set_character_state(CharacterState::WakeUp, &game_config);
+                }
+            }
+        }
+
+        //Then tick the animations to see if we have finished any and we need to be in a new state
+
+        self.characters[character_index].current_animation.sprite_timer.tick();
+        if self.characters[character_index].current_animation.sprite_timer.finished() {
+            self.characters[character_index].current_animation.current_frame += 1;
+            self.characters[character_index].current_animation.sprite_timer.reset();
+
+
+            //If we have finished the animation move the character into the
+            //next state, be that loop(like idle or run)
+            //or a steady state like Attack -> Idle
+            let frame_count = game_config.get_animation_config(self.characters[character_index].animation_state).frame_count();
+            if self.characters[character_index].current_animation.is_done(frame_count) {
+                self.characters[character_index].current_animation.reset();
+                let new_state = {
+                    self.characters[character_index].finished_animation_whats_next()
+                };
+                self.characters[character_index].set_character_state(new_state, &game_config);
+            }
+        }
+
+        let frame_input = ScreenSideAdjustedInput::new(&frame_input, self.characters[character_index].screen_side);
+
+        //While on the ground the character can pick a quick rise by pressing down, or a delayed rise by pressing a punch
+        if self.characters[character_index].character_state == CharacterState::KnockedDown && self.characters[character_index].rise_chosen == false {
+            if frame_input.down_key_down {
+                self.characters[character_index].rise_chosen = true;
+                self.characters[character_index].recovery_frames = self.characters[character_index].recovery_frames.min(QUICK_RISE_FRAMES);
+            }
+            else if frame_input.light_attack || frame_input.medium_attack || frame_input.heavy_attack {
+                self.characters[character_index].rise_chosen = true;
+                self.characters[character_index].recovery_frames += DELAYED_RISE_FRAMES;
+            }
+        }
+
+        //A thrown character can only try to tech the throw, if they do not before the window closes the throw lands
+        //The tech has to be pressed inside the window, holding throw from before the grab does not count
+        if self.characters[character_index].character_state == CharacterState::Thrown {
+            let previous_input = self.characters[character_index].input_recognizer.last_input();
+            self.characters[character_index].input_recognizer.record_input(frame_input);
+            if frame_input.pressed_since(previous_input).throw {
+                self.tech_throw(game_config);
+            }
+            else {
+                self.characters[character_index].throw_tech_frames = self.characters[character_index].throw_tech_frames.saturating_sub(1);
+                if self.characters[character_index].throw_tech_frames == 0 {
+                    self.land_throw(1 - character_index, character_index, game_config);
+                }
+            }
+            return;
+        }
+
+        //The thrower is held in the throw until it lands or gets teched
+        if self.characters[character_index].character_state == CharacterState::Throwing && self.characters[character_index].throw_tech_frames > 0 {
+            return;
+        }
+
+        let previous_input = self.characters[character_index].input_recognizer.last_input();
+        let character_action = self.characters[character_index].process_new_input(frame_input.clone(), &game_config.combo_library);
+        //We want an hierarcy of input to handle people button mashing
+        //A character should generally be Attacking Over Moving Over Doing Nothing
+
+        //Attacks can be started from neutral, or from inside a cancel window of the move the character is already doing
+        //Cancelling takes a fresh press, a held button would otherwise chain a move into itself on every hit
+        if let Some(attack_state) = character_action.attack_state() {
+            let is_new_press = character_action.is_pressed(&frame_input.pressed_since(previous_input));
+            if self.characters[character_index].can_attack() || (is_new_press && self.characters[character_index].can_cancel_into(attack_state, game_config)) {
+                self.characters[character_index].set_character_state(attack_state, &game_config);
+            }
+        }
+
+        //Holding back while the other character is attacking puts the character into a block
+        //holding down back as well makes it a crouching block
+        let opponent_index = 1 - character_index;
+        let attack_incoming = self.characters[opponent_index].is_attacking() || self.projectiles.iter().any(|x|x.team == opponent_index);
+        if self.characters[character_index].can_block() {
+            if frame_input.backward_down && attack_incoming {
+                let is_crouched = frame_input.down_key_down;
+                if self.characters[character_index].character_state != CharacterState::Blocking || self.characters[character_index].is_crouched != is_crouched {
+                    self.characters[character_index].is_crouched = is_crouched;
+                    self.characters[character_index].set_character_state(CharacterState::Blocking, &game_config);
+                }
+            }
+            else if self.characters[character_index].character_state == CharacterState::Blocking && self.characters[character_index].recovery_frames == 0 {
+                self.characters[character_index].set_character_state(CharacterState::Idle, &game_config);
+            }
+        }
+
+        if self.characters[character_index].character_state == CharacterState::Special1 {
+            //TODO: make this, idk, something better, the fact that I just need to memorize what this
+            //index is is BAD
+            if self.characters[character_index].current_animation.current_frame == 6 
+                && self.characters[character_index].current_animation.sprite_timer.current_frame == 0 {
+                let mut velocity = FixedVector2::new(Fixed::from_int(-10), Fixed::ZERO);
+                let start_offset;
+                if self.characters[character_index].screen_side == ScreenSide::Left {
+                    velocity.x = Fixed::from_int(10);
+                    start_offset = FixedVector2::zero();
+                }
+                else {
+                    start_offset = FixedVector2::zero();
+                }
+                
+                let hit_properties = self.characters[character_index].get_current_hit_properties(game_config);
+                let fireball = Projectile::new(self.characters[character_index].character_position + start_offset, velocity, self.characters[character_index].screen_side, character_index, hit_properties);
+                self.projectiles.push(fireball);
+            }
+        }
+        //If we are in the normal crouched animation, Idle + IsCrouched, and we are no longer holding the down key
+        //Stand the character up
+        //TODO: add in a "standing_up" animation state and animation
+        if self.characters[character_index].character_state == CharacterState::Idle && self.characters[character_index].is_crouched && character_action != CharacterAction::Crouch {
+            self.characters[character_index].is_crouched = false;
+            self.characters[character_index].set_character_state(CharacterState::Idle, &game_config);
+        }
+
+        if self.characters[character_index].character_state == CharacterState::Idle || self.characters[character_index].character_state == CharacterState::BackwardRun || self.characters[character_index].character_state == CharacterState::ForwardRun {
+            if character_action == CharacterAction::DashForward{
+                self.characters[character_index].set_character_state(CharacterState::ForwardDash, &game_config);
+            }
+            else if character_action == CharacterAction::DashBackward {
+                self.characters[character_index].set_character_state(CharacterState::BackwardDash, &game_config);
+            }
+            else if character_action == CharacterAction::MoveForward && self.characters[character_index].character_state != CharacterState::ForwardRun {
+                self.characters[character_index].set_character_state(CharacterState::ForwardRun, &game_config);
+            }
+            else if character_action == CharacterAction::MoveBackward && self.characters[character_index].character_state != CharacterState::BackwardRun {
+                self.characters[character_index].set_character_state(CharacterState::BackwardRun, &game_config);
+            }
+            else if character_action == CharacterAction::Crouch {
+                if self.characters[character_index].is_crouched == false {
+                    self.characters[character_index].is_crouched = true;
+                    self.characters[character_index].set_character_state(CharacterState::Crouching, &game_config);
+                }
+            }
+            else if character_action == CharacterAction::Jump {
+                self.characters[character_index].set_character_state(CharacterState::Jump, &game_config);
+                self.characters[character_index].is_jumping = true;
+                self.characters[character_index].character_velocity.y = JUMP_VELOCITY;
+            }
+            else if character_action == CharacterAction::ForwardJump {
+                self.characters[character_index].set_character_state(CharacterState::ForwardJump, &game_config);
+                let value = self.characters[character_index].screen_side.direction();
+                self.characters[character_index].set_move_starting_screen_side(value);
+                self.characters[character_index].is_jumping = true;
+                self.characters[character_index].character_velocity.y = JUMP_VELOCITY;
+            }
+        }
+
+        if self.characters[character_index].character_state == CharacterState::ForwardRun || self.characters[character_index].character_state == CharacterState::BackwardRun {
+            if character_action == CharacterAction::None {
+                self.characters[character_index].set_character_state(CharacterState::Idle, &game_config);
+            }
+        }
+
+
+        
+        //Lasting state doing state based actions like, moving
+        if self.characters[character_index].character_state == CharacterState::ForwardRun {
+            self.characters[character_index].character_velocity.y = Fixed::ZERO;
+            self.characters[character_index].character_velocity.x = -(CHARACTER_X_SPEED * self.characters[character_index].screen_side.direction());
+        }
+        else if self.characters[character_index].character_state == CharacterState::BackwardRun {
+            self.characters[character_index].character_velocity.y = Fixed::ZERO;
+            self.characters[character_index].character_velocity.x = CHARACTER_X_SPEED * self.characters[character_index].screen_side.direction();
+        }
+        else if self.characters[character_index].character_state == CharacterState::ForwardDash {
+            self.characters[character_index].character_velocity.y = Fixed::ZERO;
+            self.characters[character_index].character_velocity.x = -(CHARACTER_X_SPEED * self.characters[character_index].screen_side.direction() * Fixed::from_int(2));
+        }
+        else if self.characters[character_index].character_state == CharacterState::BackwardDash {
+            self.characters[character_index].character_velocity.y = Fixed::ZERO;
+            self.characters[character_index].character_velocity.x = CHARACTER_X_SPEED * self.characters[character_index].screen_side.direction() * Fixed::from_int(2);
+        }
+        else if self.characters[character_index].character_state == CharacterState::LightHitRecovery || self.characters[character_index].character_state == CharacterState::Blocking {
+            self.characters[character_index].character_velocity.y = Fixed::ZERO;
+            self.characters[character_index].character_velocity.x = self.characters[character_index].pushback * self.characters[character_index].screen_side.direction();
+        }
+        else if self.characters[character_index].character_state == CharacterState::Parried {
+            self.characters[character_index].character_velocity.x = -(CHARACTER_X_SPEED * self.characters[character_index].screen_side.direction()) * Fixed::from_int(5);
+            self.characters[character_index].character_velocity.y = Fixed::ZERO;
+        }
+        else if self.characters[character_index].character_state == CharacterState::ForwardJump { 
+            self.characters[character_index].character_velocity.x = game_config.character_sheet.animations[&String::from("ForwardJump")].displacements[self.characters[character_index].current_animation.current_frame as usize].x * self.characters[character_index].move_starting_screen_side;
+            self.characters[character_index].character_velocity.y -= FORWARD_JUMP_GRAVITY;
+        }
+        else if self.characters[character_index].character_state == CharacterState::Jump { 
+            self.characters[character_index].character_velocity.y -= JUMP_GRAVITY;
+        }
+        else if self.characters[character_index].character_state == CharacterState::AirHit {
+            self.characters[character_index].character_velocity.x = self.characters[character_index].pushback * self.characters[character_index].screen_side.direction();
+            self.characters[character_index].character_velocity.y -= AIR_HIT_GRAVITY;
+        }
+        else {
+            self.characters[character_index].character_velocity.x = Fixed::ZERO;
+            self.characters[character_index].character_velocity.y = Fixed::ZERO;
+        }
+
+    }
+
+    pub fn do_damage_to_character(&mut self, character_index: usize, hit_properties: &HitProperties, was_a_parry: bool, game_config: &GameplayConfig) {
+        let is_crouched = self.characters[character_index].is_crouched;
+        match self.characters[character_index].character_state {
+            //Only a block with the right guard stops the hit, otherwise it lands like normal
+            CharacterState::Blocking if hit_properties.guard.can_be_blocked(is_crouched) => {
+                if self.characters[character_index].health <= hit_properties.chip_damage {
+                    self.characters[character_index].health = 0;
+                }
+                else {
+                    self.characters[character_index].health -= hit_properties.chip_damage;
+                }
+                //Blocking costs chip damage but never builds stun, only hits that land push a character towards dizzy
+                self.characters[character_index].set_character_state(CharacterState::Blocking, &game_config);
+                self.characters[character_index].recovery_frames = hit_properties.blockstun;
+                self.characters[character_index].pushback = hit_properties.pushback;
+            }
+            _ => {
+                //Hits that land while the character is still in hitstun extend the combo, and get scaled
+                let attacker_index = 1 - character_index;
+                if self.characters[character_index].is_in_hitstun() == false {
+                    self.combo_counters[attacker_index].start();
+                }
+                let damage = game_config.scale_combo_damage(hit_properties.damage, self.combo_counters[attacker_index].hits);
+                self.combo_counters[attacker_index].add_hit(damage);
+
+                if self.characters[character_index].health <= damage {
+                    self.characters[character_index].health = 0;
+                }
+                else {
+                    self.characters[character_index].health -= damage;
+                }
+                self.characters[character_index].stun += hit_properties.stun;
+                if was_a_parry  {
+                    self.characters[character_index].set_character_state(CharacterState::Parried, &game_config);
+                }
+                //Anyone hit in the air, or by a move that knocks down, goes flying
+                else if self.characters[character_index].is_airborne() || hit_properties.reaction != HitReaction::Stagger {
+                    let mut velocity_y = AIR_HIT_POP_VELOCITY;
+                    if hit_properties.reaction == HitReaction::Launch {
+                        velocity_y = hit_properties.launch_velocity;
+                    }
+                    if self.characters[character_index].character_state == CharacterState::AirHit {
+                        self.characters[character_index].juggle_count += 1;
+                    }
+                    else {
+                        self.characters[character_index].juggle_count = 1;
+                    }
+                    self.characters[character_index].is_crouched = false;
+                    self.characters[character_index].set_character_state(CharacterState::AirHit, &game_config);
+                    self.characters[character_index].character_velocity.y = velocity_y;
+                    self.characters[character_index].recovery_frames = 0;
+                    self.characters[character_index].pushback = hit_properties.pushback;
+                }
+                else {
+                    if self.characters[character_index].character_state != CharacterState::Parried {
+                        self.characters[character_index].set_character_state(CharacterState::LightHitRecovery, &game_config);
+                        self.characters[character_index].recovery_frames = hit_properties.hitstun;
+                        self.characters[character_index].pushback = hit_properties.pushback;
+                    }
+                    else {
+                        //TODO: Handle a "HEAVY HIT RECOVERY"
+                    }
+                }
+            }
+        }
+    }
+
+    //The thrower has grabbed the other character, they now have a short window to tech it
+    pub fn start_throw(&mut self, thrower_index: usize, thrown_index: usize, game_config: &GameplayConfig) {
+        self.characters[thrown_index].is_crouched = false;
+        self.characters[thrown_index].set_character_state(CharacterState::Thrown, &game_config);
+        self.characters[thrown_index].throw_tech_frames = THROW_TECH_WINDOW;
+        //The thrower is locked for the same window, it is cleared again when the throw lands or is teched
+        self.characters[thrower_index].throw_tech_frames = THROW_TECH_WINDOW;
+        self.characters[thrower_index].character_velocity = FixedVector2::zero();
+        self.characters[thrown_index].character_velocity = FixedVector2::zero();
+    }
+
+    //The thrown character did not tech in time
+    pub fn land_throw(&mut self, thrower_index: usize, thrown_index: usize, game_config: &GameplayConfig) {
+        let hit_properties = game_config.get_hit_properties(AnimationState::Throw);
+        self.characters[thrown_index].set_character_state(CharacterState::Idle, &game_config);
+        self.do_damage_to_character(thrown_index, &hit_properties, false, game_config);
+        self.hit_stun_counter += hit_properties.hit_stop as usize;
+        self.characters[thrower_index].throw_tech_frames = 0;
+        if self.characters[thrower_index].character_state == CharacterState::Throwing {
+            self.characters[thrower_index].set_character_state(CharacterState::Idle, &game_config);
+        }
+    }
+
+    //A teched throw does no damage and pushes both characters apart
+    pub fn tech_throw(&mut self, game_config: &GameplayConfig) {
+        for character in self.characters.iter_mut() {
+            character.is_crouched = false;
+            character.throw_tech_frames = 0;
+            character.set_character_state(CharacterState::Blocking, &game_config);
+            character.recovery_frames = THROW_TECH_PUSHBACK_FRAMES;
+            character.pushback = THROW_TECH_PUSHBACK;
+        }
+    }
+
+    //Advances the round, swapping in a fresh one once this one has finished its reset timer
+    //This is the frame the game runs, anything re-simulating a match has to run the same thing
+    pub fn advance_match(&mut self, inputs: [NetInput; 2], game_config: &GameplayConfig) {
+        self.advance(inputs, game_config);
+        if self.round_done && self.reset_round_timer.finished() {
+            *self = Round::default();
+        }
+    }
+
+    pub fn new_with_animation_lib(animation_lib: &HashMap<AnimationState, AnimationConfig>) -> Round {
+        //Build up the character by loading animations for each of the animation states
+        let mut character_1 = Character::default();
+        character_1.screen_side = ScreenSide::Right;
+        character_1.character_position.x = STAGE_WIDTH / Fixed::from_int(2) - Fixed::from_ratio(FRAME_WIDTH as i32, 2) + Fixed::from_ratio(FRAME_WIDTH as i32, 4); 
+
+        character_1.current_animation = AnimationPlayer::from_config(&animation_lib[&AnimationState::Idle]);
+        let mut character_2 = Character::default();
+        character_2.screen_side = ScreenSide::Left;
+        character_2.character_position.x = STAGE_WIDTH / Fixed::from_int(2) - Fixed::from_ratio(FRAME_WIDTH as i32, 2) - Fixed::from_ratio(FRAME_WIDTH as i32, 4); // - (FRAME_WIDTH as f32) / 4.0;
+        character_2.current_animation = AnimationPlayer::from_config(&animation_lib[&AnimationState::Idle]);
+        Round {
+            characters: [character_1, character_2],
+            frame: 0,
+            round_timer: SpriteTimer::new(60 * 60),
+            round_done: false,
+            hit_stun_counter: 0,
+            projectiles: FixedList::new(),
+            reset_round_timer: SpriteTimer::new(5 * 60),
+            effects: FixedList::new(),
+            combo_counters: [ComboCounter::new(); 2]
+        }
+    }
+}
+
+impl Default for Round{
+    fn default() -> Round {
+        //Build up the character by loading animations for each of the animation states
+        let mut character_1 = Character::default();
+        character_1.screen_side = ScreenSide::Right;
+        character_1.character_position.x = STAGE_WIDTH / Fixed::from_int(2) - Fixed::from_ratio(FRAME_WIDTH as i32, 2) + Fixed::from_ratio(FRAME_WIDTH as i32, 4); 
+
+        character_1.current_animation = AnimationPlayer::new(3);
+        let mut character_2 = Character::default();
+        character_2.screen_side = ScreenSide::Left;
+        character_2.character_position.x = STAGE_WIDTH / Fixed::from_int(2) - Fixed::from_ratio(FRAME_WIDTH as i32, 2) - Fixed::from_ratio(FRAME_WIDTH as i32, 4); // - (FRAME_WIDTH as f32) / 4.0;
+        character_2.current_animation = AnimationPlayer::new(3);
+        Round {
+            characters: [character_1, character_2],
+            frame: 0,
+            round_timer: SpriteTimer::new(60 * 60),
+            round_done: false,
+            hit_stun_counter: 0,
+            projectiles: FixedList::new(),
+            reset_round_timer: SpriteTimer::new(3 * 60),
+            effects: FixedList::new(),
+            combo_counters: [ComboCounter::new(); 2]
+        }
+    }
+}
+
+impl StateHash for Round {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.characters[..].hash_state(hasher);
+        hasher.write_i32(self.frame);
+        self.round_timer.hash_state(hasher);
+        hasher.write_bool(self.round_done);
+        hasher.write_usize(self.hit_stun_counter);
+        self.projectiles.hash_state(hasher);
+        self.reset_round_timer.hash_state(hasher);
+        self.effects.hash_state(hasher);
+        self.combo_counters[..].hash_state(hasher);
+    }
+}
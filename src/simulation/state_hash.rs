@@ -0,0 +1,182 @@
+use super::*;
+
+const HASH_SEED: u64 = 0xcbf29ce484222325;
+const HASH_PRIME: u64 = 0x100000001b3;
+
+//A 64 bit hash of the simulation state, used to spot desyncs
+//It is written out by hand instead of using std::hash so the result is the same on every platform,
+//every build and every compiler version, the native client and the wasm build have to agree
+pub struct StateHasher {
+    state: u64
+}
+
+impl StateHasher {
+    pub fn new() -> StateHasher {
+        StateHasher {
+            state: HASH_SEED
+        }
+    }
+
+    //FNV-1a, but a whole word at a time instead of a byte at a time
+    pub fn write_u64(&mut self, value: u64) {
+        self.state ^= value;
+        self.state = self.state.wrapping_mul(HASH_PRIME);
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.write_u32(value as u32);
+    }
+
+    //usize is 32 bits on wasm, so it is always widened
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u64(value as u64);
+    }
+
+    //Eight bytes at a time, the length goes in too so that trailing zeros still change the hash
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in chunks.by_ref() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+        for byte in chunks.remainder() {
+            self.write_u64(*byte as u64);
+        }
+        self.write_usize(bytes.len());
+    }
+
+    //Word at a time FNV does not mix the high bits well, so run it through the splitmix64 finalizer
+    pub fn finish(&self) -> u64 {
+        let mut hash = self.state;
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+        return hash ^ (hash >> 31);
+    }
+}
+
+impl Default for StateHasher {
+    fn default() -> StateHasher {
+        return StateHasher::new();
+    }
+}
+
+//Implemented by everything that lives inside the Round, every field that can change during a round has to be fed in
+pub trait StateHash {
+    fn hash_state(&self, hasher: &mut StateHasher);
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        self.hash_state(&mut hasher);
+        return hasher.finish();
+    }
+}
+
+impl StateHash for Fixed {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_i32(self.to_bits());
+    }
+}
+
+impl StateHash for FixedVector2 {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.x.hash_state(hasher);
+        self.y.hash_state(hasher);
+    }
+}
+
+impl StateHash for SpriteTimer {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u32(self.total_frames);
+        hasher.write_u32(self.current_frame);
+        hasher.write_bool(self.finished);
+    }
+}
+
+impl StateHash for AnimationPlayer {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.sprite_timer.hash_state(hasher);
+        hasher.write_u32(self.current_frame);
+    }
+}
+
+impl<T: StateHash> StateHash for [T] {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_usize(self.len());
+        for item in self.iter() {
+            item.hash_state(hasher);
+        }
+    }
+}
+
+impl<T: StateHash + Copy + Default, const N: usize> StateHash for FixedList<T, N> {
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self[..].hash_state(hasher);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(write: impl Fn(&mut StateHasher)) -> u64 {
+        let mut hasher = StateHasher::new();
+        write(&mut hasher);
+        return hasher.finish();
+    }
+
+    #[test]
+    fn the_same_writes_give_the_same_hash() {
+        let writes = |x: &mut StateHasher| {
+            x.write_u32(7);
+            x.write_bool(true);
+            x.write_usize(42);
+        };
+        assert_eq!(hash(writes), hash(writes));
+    }
+
+    //Both clients have to come up with the same numbers, so the hash can never change between builds or platforms
+    #[test]
+    fn hashes_are_pinned() {
+        assert_eq!(StateHasher::new().finish(), 0xf52a15e9a9b5e89b);
+        assert_eq!(hash(|x| {
+            x.write_u64(1);
+            x.write_i32(-1);
+            x.write_usize(3);
+        }), 0x95b70219980fc1b5);
+    }
+
+    #[test]
+    fn order_and_values_matter() {
+        assert_ne!(hash(|x| { x.write_u32(1); x.write_u32(2); }), hash(|x| { x.write_u32(2); x.write_u32(1); }));
+        assert_ne!(hash(|x| x.write_bool(false)), hash(|x| x.write_bool(true)));
+        assert_ne!(hash(|x| x.write_i32(-1)), hash(|x| x.write_i32(1)));
+        assert_ne!(hash(|_| {}), hash(|x| x.write_u64(0)));
+    }
+
+    #[test]
+    fn bytes_are_pinned() {
+        assert_eq!(hash(|x| x.write_bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 9])), 0x285217301f8f6fab);
+    }
+
+    #[test]
+    fn trailing_zero_bytes_change_the_hash() {
+        assert_ne!(hash(|x| x.write_bytes(&[1, 2, 3])), hash(|x| x.write_bytes(&[1, 2, 3, 0])));
+        assert_ne!(hash(|x| x.write_bytes(&[0; 8])), hash(|x| x.write_bytes(&[0; 16])));
+    }
+
+    #[test]
+    fn slices_hash_their_length() {
+        let values = [Fixed::ONE, Fixed::ZERO];
+        assert_ne!(values[..1].state_hash(), values[..2].state_hash());
+        assert_eq!(values[..].state_hash(), [Fixed::ONE, Fixed::ZERO][..].state_hash());
+    }
+}